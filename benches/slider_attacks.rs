@@ -0,0 +1,70 @@
+//! Compares the slider attack backends, both on raw lookups and on perft.
+//! Perft can only use the backend the library was built with, so compare it by running the benchmark once per backend:
+//! `cargo bench --features magic` and `cargo bench --features pext`, or `RUSTFLAGS="-C target-cpu=native" cargo bench` to get the default for this CPU.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use chess::attacks::{self, magic, Slider};
+use chess::bitboard::Bitboard;
+use chess::board::{Board, START_FEN};
+use chess::perft::perft;
+use chess::rng::Rng;
+use chess::uci::Square;
+
+const KIWIPETE: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+
+/// Runs the function repeatedly for about a second, and returns the best time of a single run.
+fn time(mut function: impl FnMut()) -> Duration {
+    let start = Instant::now();
+    let mut best = Duration::MAX;
+    while start.elapsed() < Duration::from_secs(1) {
+        let run = Instant::now();
+        function();
+        best = best.min(run.elapsed());
+    }
+    best
+}
+
+fn bench_lookups(name: &str, lookup: fn(Slider, Square, Bitboard) -> Bitboard, occupancies: &[Bitboard]) {
+    // Look everything up once first so building the tables isn't timed
+    lookup(Slider::Rook, Square::new(0, 0), Bitboard::EMPTY);
+    lookup(Slider::Bishop, Square::new(0, 0), Bitboard::EMPTY);
+    let lookups = occupancies.len() * 64 * 2;
+    let best = time(|| {
+        for &occupied in occupancies {
+            for index in 0..64 {
+                let square = Square::from_index(index).unwrap();
+                black_box(lookup(Slider::Rook, square, black_box(occupied)));
+                black_box(lookup(Slider::Bishop, square, black_box(occupied)));
+            }
+        }
+    });
+    println!("{name:>6} lookups: {:>7.2} ns each", best.as_nanos() as f64 / lookups as f64);
+}
+
+fn bench_perft(name: &str, fen: &str, depth: u32) {
+    let board = Board::from_fen(fen).unwrap();
+    let nodes = perft(&board, depth);
+    let best = time(|| {
+        black_box(perft(black_box(&board), depth));
+    });
+    println!("{name:>9} perft({depth}): {nodes} nodes in {:>6.1} ms, {:>5.1} Mnps", best.as_secs_f64() * 1000.0, nodes as f64 / best.as_secs_f64() / 1e6);
+}
+
+fn main() {
+    let mut rng = Rng::new(0);
+    let occupancies: Vec<Bitboard> = (0..1000).map(|_| Bitboard(rng.next_u64() & rng.next_u64())).collect();
+
+    bench_lookups("magic", magic::slider_attacks, &occupancies);
+    #[cfg(target_arch = "x86_64")]
+    if attacks::pext::is_supported() {
+        bench_lookups("pext", attacks::pext::slider_attacks, &occupancies);
+    } else {
+        println!("  pext lookups: not supported on this CPU");
+    }
+
+    println!("perft is using the {} backend", attacks::BACKEND);
+    bench_perft("start", START_FEN, 4);
+    bench_perft("kiwipete", KIWIPETE, 3);
+}
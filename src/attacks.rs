@@ -0,0 +1,96 @@
+//! Which squares each kind of piece attacks from a given square.
+
+use crate::bitboard::Bitboard;
+use crate::board::Color;
+use crate::uci::Square;
+
+pub mod classical;
+pub mod magic;
+#[cfg(target_arch = "x86_64")]
+pub mod pext;
+
+#[cfg(all(feature = "pext", not(feature = "magic"), not(target_arch = "x86_64")))]
+compile_error!("the \"pext\" feature needs an x86_64 target");
+
+// pext is used when the compiler is allowed to assume BMI2, as with `-C target-cpu=native` on a CPU that has it.
+// The "pext" and "magic" features override that choice. If both are enabled, "magic" wins, since it works everywhere.
+#[cfg(all(not(feature = "magic"), any(feature = "pext", all(target_arch = "x86_64", target_feature = "bmi2"))))]
+use pext as backend;
+#[cfg(not(all(not(feature = "magic"), any(feature = "pext", all(target_arch = "x86_64", target_feature = "bmi2")))))]
+use magic as backend;
+
+/// The name of the slider attack backend this build uses, either "pext" or "magic".
+pub const BACKEND: &str = backend::NAME;
+
+const KNIGHT_OFFSETS: [(i8, i8); 8] = [(1, 2), (2, 1), (2, -1), (1, -2), (-1, -2), (-2, -1), (-2, 1), (-1, 2)];
+const KING_OFFSETS: [(i8, i8); 8] = [(0, 1), (1, 1), (1, 0), (1, -1), (0, -1), (-1, -1), (-1, 0), (-1, 1)];
+
+/// Builds the table of squares a piece that jumps by fixed offsets attacks from each square.
+const fn leaper_table(offsets: &[(i8, i8); 8]) -> [Bitboard; 64] {
+    let mut table = [Bitboard::EMPTY; 64];
+    let mut index = 0;
+    while index < 64 {
+        let file = (index % 8) as i8;
+        let rank = (index / 8) as i8;
+        let mut attacks = 0;
+        let mut offset = 0;
+        while offset < offsets.len() {
+            let (target_file, target_rank) = (file + offsets[offset].0, rank + offsets[offset].1);
+            if target_file >= 0 && target_file < 8 && target_rank >= 0 && target_rank < 8 {
+                attacks |= 1 << (target_rank * 8 + target_file);
+            }
+            offset += 1;
+        }
+        table[index] = Bitboard(attacks);
+        index += 1;
+    }
+    table
+}
+
+const fn pawn_table(color: Color) -> [Bitboard; 64] {
+    let mut table = [Bitboard::EMPTY; 64];
+    let mut index = 0;
+    while index < 64 {
+        table[index] = Bitboard(1 << index).pawn_attacks(color);
+        index += 1;
+    }
+    table
+}
+
+static KNIGHT_ATTACKS: [Bitboard; 64] = leaper_table(&KNIGHT_OFFSETS);
+static KING_ATTACKS: [Bitboard; 64] = leaper_table(&KING_OFFSETS);
+static PAWN_ATTACKS: [[Bitboard; 64]; 2] = [pawn_table(Color::White), pawn_table(Color::Black)];
+
+pub fn knight_attacks(square: Square) -> Bitboard {
+    KNIGHT_ATTACKS[square.index()]
+}
+
+pub fn king_attacks(square: Square) -> Bitboard {
+    KING_ATTACKS[square.index()]
+}
+
+/// The squares a pawn of the given color attacks from the square. Doesn't include where it can move without capturing.
+pub fn pawn_attacks(square: Square, color: Color) -> Bitboard {
+    PAWN_ATTACKS[color.index()][square.index()]
+}
+
+/// The two kinds of sliding piece with their own attack tables. Queens attack like both at once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Slider {
+    Rook,
+    Bishop
+}
+
+/// The squares a rook attacks, given every occupied square on the board. Includes occupied squares it could capture on, whoever they belong to.
+pub fn rook_attacks(square: Square, occupied: Bitboard) -> Bitboard {
+    backend::slider_attacks(Slider::Rook, square, occupied)
+}
+
+/// Like rook_attacks, but along the diagonals.
+pub fn bishop_attacks(square: Square, occupied: Bitboard) -> Bitboard {
+    backend::slider_attacks(Slider::Bishop, square, occupied)
+}
+
+pub fn queen_attacks(square: Square, occupied: Bitboard) -> Bitboard {
+    rook_attacks(square, occupied) | bishop_attacks(square, occupied)
+}
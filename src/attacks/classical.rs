@@ -0,0 +1,63 @@
+//! Slider attacks found by scanning along each ray until it hits something.
+//! Slower than the lookup tables, but simple enough to trust, so it's used to build the tables and to check them.
+
+use super::Slider;
+use crate::bitboard::Bitboard;
+use crate::uci::Square;
+
+/// The 8 directions a slider can move in. The first 4 go towards higher square indices.
+const DIRECTIONS: [(i8, i8); 8] = [(0, 1), (1, 1), (1, 0), (-1, 1), (0, -1), (-1, -1), (-1, 0), (1, -1)];
+
+/// For each direction and square, every square a slider would cross moving that way across an empty board.
+static RAYS: [[Bitboard; 64]; 8] = ray_table();
+
+const fn ray_table() -> [[Bitboard; 64]; 8] {
+    let mut table = [[Bitboard::EMPTY; 64]; 8];
+    let mut direction = 0;
+    while direction < 8 {
+        let (file_step, rank_step) = DIRECTIONS[direction];
+        let mut index = 0;
+        while index < 64 {
+            let mut file = (index % 8) as i8 + file_step;
+            let mut rank = (index / 8) as i8 + rank_step;
+            let mut ray = 0;
+            while file >= 0 && file < 8 && rank >= 0 && rank < 8 {
+                ray |= 1 << (rank * 8 + file);
+                file += file_step;
+                rank += rank_step;
+            }
+            table[direction][index] = Bitboard(ray);
+            index += 1;
+        }
+        direction += 1;
+    }
+    table
+}
+
+/// The squares a slider attacks in one direction, stopping at (and including) the first occupied square.
+fn ray_attacks(square: Square, occupied: Bitboard, direction: usize) -> Bitboard {
+    let ray = RAYS[direction][square.index()];
+    let blockers = (ray & occupied).0;
+    if blockers == 0 {
+        return ray;
+    }
+    // The nearest blocker is the lowest one on rays going up the board, and the highest one on rays going down
+    let nearest = if direction < 4 { blockers.trailing_zeros() } else { 63 - blockers.leading_zeros() };
+    ray ^ RAYS[direction][nearest as usize]
+}
+
+pub fn rook_attacks(square: Square, occupied: Bitboard) -> Bitboard {
+    ray_attacks(square, occupied, 0) | ray_attacks(square, occupied, 2) | ray_attacks(square, occupied, 4) | ray_attacks(square, occupied, 6)
+}
+
+pub fn bishop_attacks(square: Square, occupied: Bitboard) -> Bitboard {
+    ray_attacks(square, occupied, 1) | ray_attacks(square, occupied, 3) | ray_attacks(square, occupied, 5) | ray_attacks(square, occupied, 7)
+}
+
+/// The squares a slider attacks, given every occupied square on the board.
+pub fn slider_attacks(slider: Slider, square: Square, occupied: Bitboard) -> Bitboard {
+    match slider {
+        Slider::Rook => rook_attacks(square, occupied),
+        Slider::Bishop => bishop_attacks(square, occupied)
+    }
+}
@@ -0,0 +1,160 @@
+//! Slider attacks looked up in precomputed tables, using "magic" multipliers to turn the relevant blockers into a table index.
+//! See https://www.chessprogramming.org/Magic_Bitboards for how it works.
+//! The tables are built the first time they're used, which takes a few milliseconds.
+
+use std::sync::LazyLock;
+
+use super::{classical, Slider};
+use crate::bitboard::Bitboard;
+use crate::rng::Rng;
+use crate::uci::Square;
+
+pub const NAME: &str = "magic";
+
+/// Found with find_magic, using `Rng::new(square)` for each square.
+const ROOK_MAGICS: [u64; 64] = [
+    0x81001142A1008000, 0x0240001000402000, 0x20801000800C2000, 0x0600060040102108,
+    0x0600020004081020, 0x0200020008041001, 0x8480090002800600, 0xA080003100004080,
+    0x0020800020400080, 0x0102804000200088, 0x6002802000801008, 0x0402800801801000,
+    0x0000800400080080, 0x4404800400800200, 0x2028800100800200, 0x8111800040800100,
+    0x4220208000804000, 0xA4D000C000C86001, 0x0005050011A000C1, 0x0428808010040800,
+    0x0401050010880100, 0x080A818012000400, 0x2000808002000100, 0x00800A0004950044,
+    0x0400800080204008, 0x0490004040002000, 0x0400401900200102, 0x8020420A00102200,
+    0x0000040080080082, 0x0106000200081004, 0x0230108400010208, 0x0020018200026C01,
+    0x0118400130800680, 0x0210802008804002, 0xA008150041002000, 0x0000090021001000,
+    0x0006002092000488, 0x0000040080800200, 0x2000811804004210, 0x0100014482000431,
+    0x4080004020004000, 0x1480201000484000, 0x2101001020010040, 0x3110002015010008,
+    0x000800C500090010, 0x060C001008020200, 0x0000010002008080, 0x11024090440A0001,
+    0x0106498032010200, 0x0040002081004100, 0x0810002005908180, 0x1200100008008080,
+    0x0802800400380280, 0x2082040002008080, 0x0101000200040100, 0x4080140449008600,
+    0x11048001210414C1, 0x0008841502002042, 0x0081110842008222, 0x0090100021002815,
+    0x000200110420480E, 0x0829000204000801, 0x0002000408212082, 0x8021004890210402
+];
+
+/// Found with find_magic, using `Rng::new(square)` for each square.
+const BISHOP_MAGICS: [u64; 64] = [
+    0x0008200800A82180, 0x02100101210210C8, 0x0008280100220040, 0x44C4040084200080,
+    0x0001104040108008, 0x45008860482D0202, 0x3910A20820440480, 0x1920140508080488,
+    0x024188108410C400, 0x2080280A04004A09, 0x010D100401B8A010, 0x0000840404860508,
+    0x0100240420086A10, 0x2008408804400642, 0x020104461004A122, 0x0420004114100200,
+    0x8040301002824400, 0x0110000450025040, 0x2802010408260200, 0x0808024082004162,
+    0x001400E080A02020, 0x0010800808010880, 0x0002000401012885, 0x9022428025043010,
+    0x0010110808200100, 0x2001480110300101, 0x0000880410002024, 0x0006080004010420,
+    0x0001010120104001, 0x088800808040600C, 0x0001011000441000, 0x020080200A010440,
+    0x0402221200202090, 0x8001012000100488, 0x0154804040040C00, 0x20021A0080180080,
+    0x0070020010020104, 0x0020142240028800, 0x00040104004200D1, 0x8214240040102110,
+    0x0082100404406110, 0x00040CA804009884, 0x0004108401001000, 0x0000002128008C00,
+    0x0288080100400408, 0x0010101001200240, 0x081030021090044A, 0xA89020C109001440,
+    0x4090411808C10000, 0x0432210110110000, 0x2289020862080800, 0xC000004108480002,
+    0x6002202008504060, 0x4084901002083180, 0x1010AA0881041082, 0x0602100152008802,
+    0x1008840082100200, 0x4010104406091124, 0x8004009504415008, 0x6303080000420201,
+    0x804C1004201AC404, 0x2550002004500092, 0x042204A008016301, 0x8040101880998084
+];
+
+/// The squares whose occupancy can change a slider's attacks from the given square.
+/// The last square of each ray is left out, since the slider attacks it whether or not anything is there.
+pub fn relevant_occupancy(slider: Slider, square: Square) -> Bitboard {
+    let attacks = classical::slider_attacks(slider, square, Bitboard::EMPTY);
+    match slider {
+        Slider::Rook => {
+            let along_rank = attacks & Bitboard::rank(square.rank()) & !(Bitboard::FILE_A | Bitboard::FILE_H);
+            let along_file = attacks & Bitboard::file(square.file()) & !(Bitboard::RANK_1 | Bitboard::RANK_8);
+            along_rank | along_file
+        },
+        Slider::Bishop => attacks & !(Bitboard::FILE_A | Bitboard::FILE_H | Bitboard::RANK_1 | Bitboard::RANK_8)
+    }
+}
+
+/// Every subset of the mask, starting with the empty set.
+fn subsets(mask: Bitboard) -> impl Iterator<Item = Bitboard> {
+    // The Carry-Rippler trick steps through the subsets by counting in only the masked bits
+    let mut next = Some(Bitboard::EMPTY);
+    std::iter::from_fn(move || {
+        let current = next?;
+        let following = Bitboard(current.0.wrapping_sub(mask.0) & mask.0);
+        next = (!following.is_empty()).then_some(following);
+        Some(current)
+    })
+}
+
+fn magic_index(occupied: Bitboard, mask: Bitboard, magic: u64, shift: u32) -> usize {
+    ((occupied & mask).0.wrapping_mul(magic) >> shift) as usize
+}
+
+/// Searches for a multiplier that maps every relevant occupancy of the square to an index without mixing up occupancies that have different attacks.
+/// Only needed to regenerate the constants in this file.
+pub fn find_magic(slider: Slider, square: Square, rng: &mut Rng) -> u64 {
+    let mask = relevant_occupancy(slider, square);
+    let shift = 64 - mask.count();
+    let occupancies: Vec<(Bitboard, Bitboard)> = subsets(mask).map(|occupied| (occupied, classical::slider_attacks(slider, square, occupied))).collect();
+    let mut table = vec![None; occupancies.len()];
+    loop {
+        // Magics with few bits set work far more often, and and-ing random numbers together gives those
+        let magic = rng.next_u64() & rng.next_u64() & rng.next_u64();
+        if (mask.0.wrapping_mul(magic) >> 56).count_ones() < 6 {
+            continue;
+        }
+        table.fill(None);
+        let works = occupancies.iter().all(|&(occupied, attacks)| {
+            let entry = &mut table[magic_index(occupied, mask, magic, shift)];
+            match entry {
+                None => {
+                    *entry = Some(attacks);
+                    true
+                },
+                Some(existing) => *existing == attacks
+            }
+        });
+        if works {
+            return magic;
+        }
+    }
+}
+
+struct MagicEntry {
+    mask: Bitboard,
+    magic: u64,
+    shift: u32,
+    /// Where this square's part of the shared attack table starts.
+    offset: usize
+}
+
+struct MagicTable {
+    entries: Vec<MagicEntry>,
+    attacks: Vec<Bitboard>
+}
+
+impl MagicTable {
+    fn new(slider: Slider, magics: &[u64; 64]) -> MagicTable {
+        let mut entries = Vec::with_capacity(64);
+        let mut attacks = Vec::new();
+        for (index, &magic) in magics.iter().enumerate() {
+            let square = Square::from_index(index as u8).expect("index is on the board");
+            let mask = relevant_occupancy(slider, square);
+            let shift = 64 - mask.count();
+            let offset = attacks.len();
+            attacks.resize(offset + (1 << mask.count()), Bitboard::EMPTY);
+            for occupied in subsets(mask) {
+                attacks[offset + magic_index(occupied, mask, magic, shift)] = classical::slider_attacks(slider, square, occupied);
+            }
+            entries.push(MagicEntry {mask, magic, shift, offset});
+        }
+        MagicTable {entries, attacks}
+    }
+
+    fn attacks(&self, square: Square, occupied: Bitboard) -> Bitboard {
+        let entry = &self.entries[square.index()];
+        self.attacks[entry.offset + magic_index(occupied, entry.mask, entry.magic, entry.shift)]
+    }
+}
+
+static ROOK_TABLE: LazyLock<MagicTable> = LazyLock::new(|| MagicTable::new(Slider::Rook, &ROOK_MAGICS));
+static BISHOP_TABLE: LazyLock<MagicTable> = LazyLock::new(|| MagicTable::new(Slider::Bishop, &BISHOP_MAGICS));
+
+/// The squares a slider attacks, given every occupied square on the board.
+pub fn slider_attacks(slider: Slider, square: Square, occupied: Bitboard) -> Bitboard {
+    match slider {
+        Slider::Rook => ROOK_TABLE.attacks(square, occupied),
+        Slider::Bishop => BISHOP_TABLE.attacks(square, occupied)
+    }
+}
@@ -0,0 +1,74 @@
+//! Slider attacks looked up in precomputed tables, using the BMI2 pext instruction to gather the relevant blockers into a table index.
+//! Unlike magic bitboards there's no multiplier to find, and pext is a single fast instruction on Intel CPUs since Haswell and AMD CPUs since Zen 3.
+//! The tables are built the first time they're used, which panics if the CPU doesn't support BMI2.
+
+use std::arch::x86_64::_pext_u64;
+use std::sync::LazyLock;
+
+use super::magic::relevant_occupancy;
+use super::{classical, Slider};
+use crate::bitboard::Bitboard;
+use crate::uci::Square;
+
+pub const NAME: &str = "pext";
+
+/// Returns true if the CPU running the program can use this backend.
+pub fn is_supported() -> bool {
+    std::arch::is_x86_feature_detected!("bmi2")
+}
+
+#[target_feature(enable = "bmi2")]
+fn pext(value: u64, mask: u64) -> u64 {
+    _pext_u64(value, mask)
+}
+
+struct PextTable {
+    masks: [Bitboard; 64],
+    /// Where each square's part of the shared attack table starts.
+    offsets: [usize; 64],
+    attacks: Vec<Bitboard>
+}
+
+impl PextTable {
+    fn new(slider: Slider) -> PextTable {
+        assert!(is_supported(), "the pext slider attacks need a CPU with BMI2");
+        let mut masks = [Bitboard::EMPTY; 64];
+        let mut offsets = [0; 64];
+        let mut attacks = Vec::new();
+        for index in 0..64 {
+            let square = Square::from_index(index as u8).expect("index is on the board");
+            let mask = relevant_occupancy(slider, square);
+            masks[index] = mask;
+            offsets[index] = attacks.len();
+            // pext packs the masked bits together in order, so counting up to 2^n and depositing the bits back gives every occupancy in index order
+            for packed in 0..1u64 << mask.count() {
+                let mut occupied = Bitboard::EMPTY;
+                for (bit, square) in mask.into_iter().enumerate() {
+                    if packed & (1 << bit) != 0 {
+                        occupied = occupied.with(square);
+                    }
+                }
+                attacks.push(classical::slider_attacks(slider, square, occupied));
+            }
+        }
+        PextTable {masks, offsets, attacks}
+    }
+
+    fn attacks(&self, square: Square, occupied: Bitboard) -> Bitboard {
+        // SAFETY: the table is only ever built after checking the CPU supports BMI2
+        let index = unsafe { pext(occupied.0, self.masks[square.index()].0) };
+        self.attacks[self.offsets[square.index()] + index as usize]
+    }
+}
+
+static ROOK_TABLE: LazyLock<PextTable> = LazyLock::new(|| PextTable::new(Slider::Rook));
+static BISHOP_TABLE: LazyLock<PextTable> = LazyLock::new(|| PextTable::new(Slider::Bishop));
+
+/// The squares a slider attacks, given every occupied square on the board.
+/// Panics if the CPU doesn't support BMI2.
+pub fn slider_attacks(slider: Slider, square: Square, occupied: Bitboard) -> Bitboard {
+    match slider {
+        Slider::Rook => ROOK_TABLE.attacks(square, occupied),
+        Slider::Bishop => BISHOP_TABLE.attacks(square, occupied)
+    }
+}
@@ -0,0 +1,13 @@
+//! The searching engine: alpha-beta negamax with iterative deepening, judging positions on material.
+
+use chess::engines::alphabeta::AlphaBeta;
+use chess::uci::UCIInterface;
+
+fn main() -> std::io::Result<()> {
+    if std::env::args().len() > 1 {
+        eprintln!("usage: alphabeta");
+        std::process::exit(2);
+    }
+
+    UCIInterface::with_stdio(AlphaBeta::new()).run()
+}
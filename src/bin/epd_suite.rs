@@ -0,0 +1,120 @@
+//! Runs an engine over a test suite in EPD, like Win At Chess, and reports which positions it solved.
+//! A position is solved if the engine plays one of its "bm" moves, avoids all of its "am" moves, and finds a mate at least as short as its "dm".
+//! Positions with none of those operations are searched but not scored.
+
+use std::time::{Duration, Instant};
+
+use chess::engines::alphabeta::AlphaBeta;
+use chess::engines::greedy::GreedyMover;
+use chess::engines::random::RandomMover;
+use chess::epd::{read_epd, Epd};
+use chess::game::Game;
+use chess::rng::Rng;
+use chess::san::to_san;
+use chess::uci::{Engine, InfoCommandData, Move, ScoreInfoData, SearchLimits, SearchSignals};
+
+const USAGE: &str = "usage: epd_suite <file> [--engine random|greedy|alphabeta] [--movetime <ms> | --depth <plies>]";
+
+fn usage() -> ! {
+    eprintln!("{USAGE}");
+    std::process::exit(2);
+}
+
+fn engine_named(name: &str) -> Option<Box<dyn Engine>> {
+    match name {
+        "random" => Some(Box::new(RandomMover::new(Rng::from_entropy()))),
+        "greedy" => Some(Box::new(GreedyMover::new(Rng::from_entropy()))),
+        "alphabeta" => Some(Box::new(AlphaBeta::new())),
+        _ => None
+    }
+}
+
+/// Whether the engine's answer solves the position, or None if the position doesn't say what the answer should be.
+fn judge(epd: &Epd, played: Move, mate: Option<isize>) -> Result<Option<bool>, String> {
+    let best = epd.best_moves().map_err(|error| error.to_string())?;
+    let avoid = epd.avoid_moves().map_err(|error| error.to_string())?;
+    let direct_mate = epd.direct_mate();
+    if best.is_empty() && avoid.is_empty() && direct_mate.is_none() {
+        return Ok(None);
+    }
+    let solved = (best.is_empty() || best.contains(&played))
+        && !avoid.contains(&played)
+        && direct_mate.is_none_or(|moves| mate.is_some_and(|mate| mate > 0 && mate as usize <= moves));
+    Ok(Some(solved))
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let Some(path) = args.next() else { usage() };
+    let mut engine_name = "random".to_string();
+    let mut limits = SearchLimits {move_time: Some(Duration::from_secs(1)), ..SearchLimits::default()};
+    while let Some(arg) = args.next() {
+        let Some(value) = args.next() else { usage() };
+        match arg.as_str() {
+            "--engine" => engine_name = value,
+            "--movetime" => {
+                let Ok(ms) = value.parse() else { usage() };
+                limits = SearchLimits {move_time: Some(Duration::from_millis(ms)), ..SearchLimits::default()};
+            },
+            "--depth" => {
+                let Ok(depth) = value.parse() else { usage() };
+                limits = SearchLimits {depth: Some(depth), ..SearchLimits::default()};
+            },
+            _ => usage()
+        }
+    }
+    let Some(mut engine) = engine_named(&engine_name) else {
+        eprintln!("unknown engine \"{engine_name}\"");
+        usage()
+    };
+
+    let text = match std::fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(error) => {
+            eprintln!("couldn't read {path}: {error}");
+            std::process::exit(1);
+        }
+    };
+    let positions = match read_epd(&text) {
+        Ok(positions) => positions,
+        Err((line, error)) => {
+            eprintln!("{path}:{line}: {error}");
+            std::process::exit(1);
+        }
+    };
+
+    let started = Instant::now();
+    let (mut solved, mut scored) = (0, 0);
+    for (index, epd) in positions.iter().enumerate() {
+        let name = epd.id().map_or_else(|| format!("#{}", index + 1), str::to_string);
+        engine.new_game();
+        engine.set_position(Game::new(epd.board.clone()));
+        let mut mate = None;
+        let best = engine.go(&limits, &SearchSignals::new(), &mut |infos| {
+            for info in infos {
+                if let InfoCommandData::Score(score) = info {
+                    mate = match score {
+                        ScoreInfoData::MateInMoves(moves) => Some(moves),
+                        ScoreInfoData::CentiPawns(_) => None,
+                        _ => mate
+                    };
+                }
+            }
+        });
+        let played = to_san(&epd.board, best.selected_move);
+        match judge(epd, best.selected_move, mate) {
+            Ok(Some(true)) => {
+                solved += 1;
+                scored += 1;
+                println!("{name}: solved with {played}");
+            },
+            Ok(Some(false)) => {
+                scored += 1;
+                println!("{name}: failed, played {played}");
+            },
+            Ok(None) => println!("{name}: played {played}, nothing to check it against"),
+            Err(error) => println!("{name}: played {played}, but the expected moves can't be read: {error}")
+        }
+    }
+    println!("solved {solved}/{scored} in {:.1}s", started.elapsed().as_secs_f64());
+}
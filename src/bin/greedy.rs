@@ -0,0 +1,22 @@
+//! A step up from the random mover: mates in one when it can, and otherwise takes the most material it can this move.
+//! Pass `--seed <number>` to make its tie-breaking reproducible.
+
+use chess::engines::greedy::GreedyMover;
+use chess::rng::Rng;
+use chess::uci::UCIInterface;
+
+fn main() -> std::io::Result<()> {
+    let mut args = std::env::args().skip(1);
+    let mut rng = Rng::from_entropy();
+    while let Some(arg) = args.next() {
+        match (arg.as_str(), args.next().map(|seed| seed.parse::<u64>())) {
+            ("--seed", Some(Ok(seed))) => rng = Rng::new(seed),
+            _ => {
+                eprintln!("usage: greedy [--seed <number>]");
+                std::process::exit(2);
+            }
+        }
+    }
+
+    UCIInterface::with_stdio(GreedyMover::new(rng)).run()
+}
@@ -0,0 +1,61 @@
+use std::time::Instant;
+
+use chess::board::{Board, START_FEN};
+use chess::perft::{divide, perft, perft_stats};
+
+const USAGE: &str = "usage: perft <depth> [fen] [--divide] [--stats]";
+
+fn main() {
+    let mut divide_mode = false;
+    let mut stats_mode = false;
+    let mut positional = Vec::new();
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--divide" => divide_mode = true,
+            "--stats" => stats_mode = true,
+            _ => positional.push(arg)
+        }
+    }
+
+    let Some(depth) = positional.first().and_then(|depth| depth.parse::<u32>().ok()) else {
+        eprintln!("{USAGE}");
+        std::process::exit(2);
+    };
+    // The FEN may be passed as one quoted argument or as separate fields
+    let fen = if positional.len() > 1 { positional[1..].join(" ") } else { START_FEN.to_string() };
+    let board = match Board::from_fen(&fen) {
+        Ok(board) => board,
+        Err(error) => {
+            eprintln!("invalid FEN: {error}");
+            std::process::exit(2);
+        }
+    };
+
+    let start = Instant::now();
+    // Divide has nothing to split at depth 0, so that's just counted as usual
+    let nodes = if divide_mode && depth > 0 {
+        let mut total = 0;
+        for (m, nodes) in divide(&board, depth) {
+            println!("{m}: {nodes}");
+            total += nodes;
+        }
+        println!();
+        total
+    } else if stats_mode {
+        let stats = perft_stats(&board, depth);
+        println!("captures: {}", stats.captures);
+        println!("en passants: {}", stats.en_passants);
+        println!("castles: {}", stats.castles);
+        println!("promotions: {}", stats.promotions);
+        println!("checks: {}", stats.checks);
+        println!("checkmates: {}", stats.checkmates);
+        stats.nodes
+    } else {
+        perft(&board, depth)
+    };
+    let elapsed = start.elapsed();
+
+    println!("nodes: {nodes}");
+    println!("time: {} ms", elapsed.as_millis());
+    println!("nps: {}", (nodes as f64 / elapsed.as_secs_f64().max(1e-9)) as u64);
+}
@@ -1,3 +1,22 @@
-fn main() {
-    println!("Hello, world!");
+//! The baseline engine: plays a uniformly random legal move, and nothing else.
+//! Pass `--seed <number>` to make its choices reproducible.
+
+use chess::engines::random::RandomMover;
+use chess::rng::Rng;
+use chess::uci::UCIInterface;
+
+fn main() -> std::io::Result<()> {
+    let mut args = std::env::args().skip(1);
+    let mut rng = Rng::from_entropy();
+    while let Some(arg) = args.next() {
+        match (arg.as_str(), args.next().map(|seed| seed.parse::<u64>())) {
+            ("--seed", Some(Ok(seed))) => rng = Rng::new(seed),
+            _ => {
+                eprintln!("usage: random_move [--seed <number>]");
+                std::process::exit(2);
+            }
+        }
+    }
+
+    UCIInterface::with_stdio(RandomMover::new(rng)).run()
 }
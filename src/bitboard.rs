@@ -0,0 +1,199 @@
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
+
+use crate::board::Color;
+use crate::uci::Square;
+
+/// A set of squares, stored as one bit per square with a1 as the lowest bit and h8 as the highest.
+/// Counting and finding squares compile down to single popcnt and tzcnt instructions when built with `-C target-cpu=native` on a CPU that has them.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Bitboard(pub u64);
+
+impl Bitboard {
+    pub const EMPTY: Bitboard = Bitboard(0);
+    pub const FULL: Bitboard = Bitboard(u64::MAX);
+    pub const FILE_A: Bitboard = Bitboard(0x0101_0101_0101_0101);
+    pub const FILE_H: Bitboard = Bitboard(0x8080_8080_8080_8080);
+    pub const RANK_1: Bitboard = Bitboard(0xFF);
+    pub const RANK_8: Bitboard = Bitboard(0xFF << 56);
+    /// The dark squares, which include a1 and h8.
+    pub const DARK_SQUARES: Bitboard = Bitboard(0xAA55_AA55_AA55_AA55);
+    pub const LIGHT_SQUARES: Bitboard = Bitboard(!0xAA55_AA55_AA55_AA55);
+
+    pub const fn from_square(square: Square) -> Bitboard {
+        Bitboard(1 << square.index())
+    }
+
+    /// Every square on the given file, counting from 0 for the a file.
+    pub const fn file(file: u8) -> Bitboard {
+        Bitboard(Bitboard::FILE_A.0 << file)
+    }
+
+    /// Every square on the given rank, counting from 0 for the first rank.
+    pub const fn rank(rank: u8) -> Bitboard {
+        Bitboard(Bitboard::RANK_1.0 << (rank * 8))
+    }
+
+    pub const fn contains(self, square: Square) -> bool {
+        self.0 & (1 << square.index()) != 0
+    }
+
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub const fn with(self, square: Square) -> Bitboard {
+        Bitboard(self.0 | 1 << square.index())
+    }
+
+    pub const fn without(self, square: Square) -> Bitboard {
+        Bitboard(self.0 & !(1 << square.index()))
+    }
+
+    /// The number of squares in the set.
+    pub const fn count(self) -> u32 {
+        self.0.count_ones()
+    }
+
+    /// The lowest square in the set, or None if it's empty.
+    pub const fn first_square(self) -> Option<Square> {
+        Square::from_index(self.0.trailing_zeros() as u8)
+    }
+
+    /// Removes and returns the lowest square in the set.
+    pub fn pop_first(&mut self) -> Option<Square> {
+        let square = self.first_square()?;
+        // Clearing the lowest set bit this way compiles to a single blsr where available
+        self.0 &= self.0 - 1;
+        Some(square)
+    }
+
+    /// Moves every square one rank up, dropping anything that falls off the board.
+    pub const fn north(self) -> Bitboard {
+        Bitboard(self.0 << 8)
+    }
+
+    pub const fn south(self) -> Bitboard {
+        Bitboard(self.0 >> 8)
+    }
+
+    /// Moves every square one file to the right, without wrapping around from the h file to the a file.
+    pub const fn east(self) -> Bitboard {
+        Bitboard((self.0 & !Bitboard::FILE_H.0) << 1)
+    }
+
+    pub const fn west(self) -> Bitboard {
+        Bitboard((self.0 & !Bitboard::FILE_A.0) >> 1)
+    }
+
+    /// Moves every square one rank towards the other side of the board, from the given side's point of view.
+    pub const fn forward(self, color: Color) -> Bitboard {
+        match color {
+            Color::White => self.north(),
+            Color::Black => self.south()
+        }
+    }
+
+    /// Every square attacked by a pawn of the given color on any of these squares.
+    pub const fn pawn_attacks(self, color: Color) -> Bitboard {
+        let forward = self.forward(color);
+        Bitboard(forward.east().0 | forward.west().0)
+    }
+}
+
+/// Iterates over the squares in a bitboard, from a1 towards h8.
+pub struct Squares(Bitboard);
+
+impl Iterator for Squares {
+    type Item = Square;
+
+    fn next(&mut self) -> Option<Square> {
+        self.0.pop_first()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let count = self.0.count() as usize;
+        (count, Some(count))
+    }
+}
+
+impl ExactSizeIterator for Squares {}
+
+impl IntoIterator for Bitboard {
+    type Item = Square;
+    type IntoIter = Squares;
+
+    fn into_iter(self) -> Squares {
+        Squares(self)
+    }
+}
+
+impl FromIterator<Square> for Bitboard {
+    fn from_iter<I: IntoIterator<Item = Square>>(squares: I) -> Self {
+        squares.into_iter().fold(Bitboard::EMPTY, Bitboard::with)
+    }
+}
+
+impl BitAnd for Bitboard {
+    type Output = Bitboard;
+
+    fn bitand(self, other: Bitboard) -> Bitboard {
+        Bitboard(self.0 & other.0)
+    }
+}
+
+impl BitOr for Bitboard {
+    type Output = Bitboard;
+
+    fn bitor(self, other: Bitboard) -> Bitboard {
+        Bitboard(self.0 | other.0)
+    }
+}
+
+impl BitXor for Bitboard {
+    type Output = Bitboard;
+
+    fn bitxor(self, other: Bitboard) -> Bitboard {
+        Bitboard(self.0 ^ other.0)
+    }
+}
+
+impl Not for Bitboard {
+    type Output = Bitboard;
+
+    fn not(self) -> Bitboard {
+        Bitboard(!self.0)
+    }
+}
+
+impl BitAndAssign for Bitboard {
+    fn bitand_assign(&mut self, other: Bitboard) {
+        self.0 &= other.0;
+    }
+}
+
+impl BitOrAssign for Bitboard {
+    fn bitor_assign(&mut self, other: Bitboard) {
+        self.0 |= other.0;
+    }
+}
+
+impl BitXorAssign for Bitboard {
+    fn bitxor_assign(&mut self, other: Bitboard) {
+        self.0 ^= other.0;
+    }
+}
+
+/// Draws the board with rank 8 at the top, which is much easier to read than a 64 bit number.
+impl std::fmt::Debug for Bitboard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Bitboard({:#018x})", self.0)?;
+        for rank in (0..8).rev() {
+            for file in 0..8 {
+                let c = if self.contains(Square::new(file, rank)) { 'X' } else { '.' };
+                write!(f, "{c}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
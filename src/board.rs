@@ -0,0 +1,740 @@
+use crate::attacks::{bishop_attacks, king_attacks, knight_attacks, pawn_attacks, rook_attacks};
+use crate::bitboard::Bitboard;
+use crate::uci::{Move, PromotionPiece, Square};
+use crate::zobrist;
+
+/// One of the two sides.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Color {
+    White,
+    Black
+}
+
+impl Color {
+    pub const fn opponent(self) -> Color {
+        match self {
+            Color::White => Color::Black,
+            Color::Black => Color::White
+        }
+    }
+
+    /// Used to index tables that have one entry per color.
+    pub const fn index(self) -> usize {
+        self as usize
+    }
+
+    /// The rank (counting from 0) this side's pieces start on.
+    pub const fn back_rank(self) -> u8 {
+        match self {
+            Color::White => 0,
+            Color::Black => 7
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PieceKind {
+    Pawn,
+    Knight,
+    Bishop,
+    Rook,
+    Queen,
+    King
+}
+
+impl PieceKind {
+    pub const ALL: [PieceKind; 6] = [PieceKind::Pawn, PieceKind::Knight, PieceKind::Bishop, PieceKind::Rook, PieceKind::Queen, PieceKind::King];
+
+    /// Used to index tables that have one entry per kind of piece.
+    pub const fn index(self) -> usize {
+        self as usize
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Piece {
+    pub color: Color,
+    pub kind: PieceKind
+}
+
+impl Piece {
+    pub const fn new(color: Color, kind: PieceKind) -> Piece {
+        Piece {color, kind}
+    }
+
+    /// Reads a piece letter as used in FEN. Uppercase is white, lowercase is black.
+    pub fn from_fen_char(c: char) -> Option<Piece> {
+        let color = if c.is_ascii_uppercase() { Color::White } else { Color::Black };
+        let kind = match c.to_ascii_lowercase() {
+            'p' => PieceKind::Pawn,
+            'n' => PieceKind::Knight,
+            'b' => PieceKind::Bishop,
+            'r' => PieceKind::Rook,
+            'q' => PieceKind::Queen,
+            'k' => PieceKind::King,
+            _ => return None
+        };
+        Some(Piece {color, kind})
+    }
+
+    pub fn to_fen_char(self) -> char {
+        let c = match self.kind {
+            PieceKind::Pawn => 'p',
+            PieceKind::Knight => 'n',
+            PieceKind::Bishop => 'b',
+            PieceKind::Rook => 'r',
+            PieceKind::Queen => 'q',
+            PieceKind::King => 'k'
+        };
+        match self.color {
+            Color::White => c.to_ascii_uppercase(),
+            Color::Black => c
+        }
+    }
+}
+
+/// Which castling moves are still allowed, stored as bit flags.
+/// Having the right doesn't mean the move is legal right now, only that neither the king nor that rook has moved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct CastlingRights(u8);
+
+impl CastlingRights {
+    pub const NONE: CastlingRights = CastlingRights(0);
+    pub const WHITE_KINGSIDE: CastlingRights = CastlingRights(1);
+    pub const WHITE_QUEENSIDE: CastlingRights = CastlingRights(2);
+    pub const BLACK_KINGSIDE: CastlingRights = CastlingRights(4);
+    pub const BLACK_QUEENSIDE: CastlingRights = CastlingRights(8);
+    pub const ALL: CastlingRights = CastlingRights(15);
+
+    pub const fn kingside(color: Color) -> CastlingRights {
+        match color {
+            Color::White => CastlingRights::WHITE_KINGSIDE,
+            Color::Black => CastlingRights::BLACK_KINGSIDE
+        }
+    }
+
+    pub const fn queenside(color: Color) -> CastlingRights {
+        match color {
+            Color::White => CastlingRights::WHITE_QUEENSIDE,
+            Color::Black => CastlingRights::BLACK_QUEENSIDE
+        }
+    }
+
+    pub const fn contains(self, other: CastlingRights) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn insert(&mut self, other: CastlingRights) {
+        self.0 |= other.0;
+    }
+
+    pub fn remove(&mut self, other: CastlingRights) {
+        self.0 &= !other.0;
+    }
+
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub const fn bits(self) -> u8 {
+        self.0
+    }
+}
+
+/// The FEN of the normal chess starting position.
+pub const START_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+/// Describes why a FEN string couldn't be turned into a Board.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FenError {
+    /// A FEN has 6 space separated fields. The last two are allowed to be missing.
+    WrongFieldCount(usize),
+    /// The piece placement must have exactly 8 ranks separated by '/'.
+    WrongRankCount(usize),
+    /// A rank described more or less than 8 squares. Ranks count from 1, like on the board.
+    WrongRankLength {rank: u8, length: usize},
+    /// A character in the piece placement wasn't a piece or a digit from 1 to 8.
+    InvalidPiece(char),
+    InvalidSideToMove(String),
+    InvalidCastlingRights(String),
+    InvalidEnPassant(String),
+    InvalidHalfmoveClock(String),
+    InvalidFullmoveNumber(String),
+    /// Each side must have exactly one king.
+    WrongKingCount {color: Color, count: usize},
+    /// Pawns can never stand on the first or last rank.
+    PawnOnBackRank(Square),
+    /// The side that just moved has left its king in check, which can't happen in a real game.
+    OpponentInCheck,
+    /// A castling right was given, but the king or rook isn't on its starting square.
+    CastlingWithoutPieces(char),
+    /// The en passant square doesn't fit a pawn having just moved two squares.
+    ImpossibleEnPassant(Square)
+}
+
+impl std::fmt::Display for FenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FenError::WrongFieldCount(count) => write!(f, "expected 6 fields but found {count}"),
+            FenError::WrongRankCount(count) => write!(f, "expected 8 ranks but found {count}"),
+            FenError::WrongRankLength {rank, length} => write!(f, "rank {rank} describes {length} squares instead of 8"),
+            FenError::InvalidPiece(c) => write!(f, "'{c}' is not a piece"),
+            FenError::InvalidSideToMove(field) => write!(f, "\"{field}\" is not w or b"),
+            FenError::InvalidCastlingRights(field) => write!(f, "\"{field}\" is not a valid set of castling rights"),
+            FenError::InvalidEnPassant(field) => write!(f, "\"{field}\" is not a valid en passant square"),
+            FenError::InvalidHalfmoveClock(field) => write!(f, "\"{field}\" is not a valid halfmove clock"),
+            FenError::InvalidFullmoveNumber(field) => write!(f, "\"{field}\" is not a valid fullmove number"),
+            FenError::WrongKingCount {color, count} => write!(f, "{color:?} has {count} kings instead of 1"),
+            FenError::PawnOnBackRank(square) => write!(f, "there is a pawn on {square}"),
+            FenError::OpponentInCheck => write!(f, "the side not to move is in check"),
+            FenError::CastlingWithoutPieces(right) => write!(f, "castling right '{right}' is given but the king or rook has moved"),
+            FenError::ImpossibleEnPassant(square) => write!(f, "no pawn can have just skipped over {square}")
+        }
+    }
+}
+
+impl std::error::Error for FenError {}
+
+/// Returns the square at the given offset from another, or None if that's off the board.
+fn offset_square(square: Square, file_offset: i8, rank_offset: i8) -> Option<Square> {
+    let file = square.file() as i8 + file_offset;
+    let rank = square.rank() as i8 + rank_offset;
+    if (0..8).contains(&file) && (0..8).contains(&rank) {
+        Some(Square::new(file as u8, rank as u8))
+    } else {
+        None
+    }
+}
+
+/// The files a castling rook moves from and to, given the king's move.
+fn castling_rook_files(king_from: Square, king_to: Square) -> (u8, u8) {
+    if king_to.file() > king_from.file() { (7, 5) } else { (0, 3) }
+}
+
+/// Everything about a position that a move can change and that can't be worked out again from the move itself.
+/// Returned by Board::make_move and handed back to Board::unmake_move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Undo {
+    /// The piece the move captured, if any. For en passant this is the pawn beside the destination square.
+    captured: Option<Piece>,
+    castling_rights: CastlingRights,
+    en_passant: Option<Square>,
+    halfmove_clock: u32,
+    hash: u64
+}
+
+/// A complete chess position: where every piece is, plus everything else a FEN records.
+/// Pieces are stored both as bitboards, for generating moves and attacks, and square by square, for quickly finding what's on a given square.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Board {
+    /// The squares occupied by each kind of piece, of either color.
+    kinds: [Bitboard; 6],
+    /// The squares occupied by each color.
+    colors: [Bitboard; 2],
+    squares: [Option<Piece>; 64],
+    side_to_move: Color,
+    castling_rights: CastlingRights,
+    en_passant: Option<Square>,
+    halfmove_clock: u32,
+    fullmove_number: u32,
+    /// The Zobrist key of the position, kept up to date as moves are made.
+    hash: u64
+}
+
+impl Board {
+    pub fn start_position() -> Board {
+        Board::from_fen(START_FEN).expect("the starting position is valid")
+    }
+
+    /// Parses and validates a FEN string.
+    /// The halfmove clock and fullmove number may be left out, in which case they default to 0 and 1.
+    pub fn from_fen(fen: &str) -> Result<Board, FenError> {
+        let fields: Vec<&str> = fen.split_whitespace().collect();
+        if fields.len() != 4 && fields.len() != 6 {
+            return Err(FenError::WrongFieldCount(fields.len()));
+        }
+
+        let mut squares = [None; 64];
+        let ranks: Vec<&str> = fields[0].split('/').collect();
+        if ranks.len() != 8 {
+            return Err(FenError::WrongRankCount(ranks.len()));
+        }
+        // FEN lists the ranks from 8 down to 1
+        for (row, rank_text) in ranks.iter().enumerate() {
+            let rank = 7 - row as u8;
+            let mut file = 0;
+            for c in rank_text.chars() {
+                if let Some(skip) = c.to_digit(10).filter(|skip| (1..=8).contains(skip)) {
+                    file += skip as usize;
+                    continue;
+                }
+                let piece = Piece::from_fen_char(c).ok_or(FenError::InvalidPiece(c))?;
+                if file < 8 {
+                    squares[Square::new(file as u8, rank).index()] = Some(piece);
+                }
+                file += 1;
+            }
+            if file != 8 {
+                return Err(FenError::WrongRankLength {rank: rank + 1, length: file});
+            }
+        }
+
+        let side_to_move = match fields[1] {
+            "w" => Color::White,
+            "b" => Color::Black,
+            field => return Err(FenError::InvalidSideToMove(field.to_string()))
+        };
+
+        let mut castling_rights = CastlingRights::NONE;
+        if fields[2] != "-" {
+            for c in fields[2].chars() {
+                let right = match c {
+                    'K' => CastlingRights::WHITE_KINGSIDE,
+                    'Q' => CastlingRights::WHITE_QUEENSIDE,
+                    'k' => CastlingRights::BLACK_KINGSIDE,
+                    'q' => CastlingRights::BLACK_QUEENSIDE,
+                    _ => return Err(FenError::InvalidCastlingRights(fields[2].to_string()))
+                };
+                if castling_rights.contains(right) {
+                    return Err(FenError::InvalidCastlingRights(fields[2].to_string()));
+                }
+                castling_rights.insert(right);
+            }
+        }
+
+        let en_passant = match fields[3] {
+            "-" => None,
+            field => Some(field.parse().map_err(|_| FenError::InvalidEnPassant(field.to_string()))?)
+        };
+
+        let (halfmove_clock, fullmove_number) = if fields.len() == 6 {
+            let halfmove_clock = fields[4].parse().map_err(|_| FenError::InvalidHalfmoveClock(fields[4].to_string()))?;
+            let fullmove_number = fields[5].parse().ok().filter(|number| *number > 0)
+                .ok_or_else(|| FenError::InvalidFullmoveNumber(fields[5].to_string()))?;
+            (halfmove_clock, fullmove_number)
+        } else {
+            (0, 1)
+        };
+
+        let mut board = Board {
+            kinds: [Bitboard::EMPTY; 6],
+            colors: [Bitboard::EMPTY; 2],
+            squares: [None; 64],
+            side_to_move,
+            castling_rights,
+            en_passant,
+            halfmove_clock,
+            fullmove_number,
+            hash: 0
+        };
+        for (index, piece) in squares.into_iter().enumerate() {
+            if let Some(piece) = piece {
+                board.put_piece(Square::from_index(index as u8).expect("index is on the board"), piece);
+            }
+        }
+        board.validate()?;
+        board.hash = board.compute_key();
+        Ok(board)
+    }
+
+    /// Checks for things a FEN can describe but a real game could never reach.
+    fn validate(&self) -> Result<(), FenError> {
+        for color in [Color::White, Color::Black] {
+            let count = self.pieces_of(color, PieceKind::King).count() as usize;
+            if count != 1 {
+                return Err(FenError::WrongKingCount {color, count});
+            }
+        }
+
+        let back_ranks = Bitboard::RANK_1 | Bitboard::RANK_8;
+        if let Some(square) = (self.kinds[PieceKind::Pawn.index()] & back_ranks).first_square() {
+            return Err(FenError::PawnOnBackRank(square));
+        }
+
+        let rights = [
+            (CastlingRights::WHITE_KINGSIDE, 'K', Color::White, 7),
+            (CastlingRights::WHITE_QUEENSIDE, 'Q', Color::White, 0),
+            (CastlingRights::BLACK_KINGSIDE, 'k', Color::Black, 7),
+            (CastlingRights::BLACK_QUEENSIDE, 'q', Color::Black, 0)
+        ];
+        for (right, name, color, rook_file) in rights {
+            if !self.castling_rights.contains(right) {
+                continue;
+            }
+            let rank = color.back_rank();
+            let king_home = self.piece_at(Square::new(4, rank)) == Some(Piece::new(color, PieceKind::King));
+            let rook_home = self.piece_at(Square::new(rook_file, rank)) == Some(Piece::new(color, PieceKind::Rook));
+            if !king_home || !rook_home {
+                return Err(FenError::CastlingWithoutPieces(name));
+            }
+        }
+
+        if let Some(square) = self.en_passant {
+            // The pawn that just moved belongs to the side not to move, and skipped over the en passant square
+            let mover = self.side_to_move.opponent();
+            let (expected_rank, forward) = match mover {
+                Color::White => (2, 1),
+                Color::Black => (5, -1)
+            };
+            let pawn_square = offset_square(square, 0, forward);
+            let start_square = offset_square(square, 0, -forward);
+            let possible = square.rank() == expected_rank
+                && self.piece_at(square).is_none()
+                && start_square.is_some_and(|start| self.piece_at(start).is_none())
+                && pawn_square.is_some_and(|pawn| self.piece_at(pawn) == Some(Piece::new(mover, PieceKind::Pawn)));
+            if !possible {
+                return Err(FenError::ImpossibleEnPassant(square));
+            }
+        }
+
+        let opponent = self.side_to_move.opponent();
+        if self.is_square_attacked(self.king_square(opponent), self.side_to_move) {
+            return Err(FenError::OpponentInCheck);
+        }
+        Ok(())
+    }
+
+    /// Writes the position as a FEN string with all 6 fields.
+    pub fn to_fen(&self) -> String {
+        let mut fen = String::new();
+        for rank in (0..8).rev() {
+            let mut empty = 0;
+            for file in 0..8 {
+                match self.piece_at(Square::new(file, rank)) {
+                    Some(piece) => {
+                        if empty > 0 {
+                            fen.push(char::from(b'0' + empty));
+                            empty = 0;
+                        }
+                        fen.push(piece.to_fen_char());
+                    },
+                    None => empty += 1
+                }
+            }
+            if empty > 0 {
+                fen.push(char::from(b'0' + empty));
+            }
+            if rank > 0 {
+                fen.push('/');
+            }
+        }
+
+        fen.push_str(match self.side_to_move {
+            Color::White => " w ",
+            Color::Black => " b "
+        });
+
+        if self.castling_rights.is_empty() {
+            fen.push('-');
+        }
+        let rights = [
+            (CastlingRights::WHITE_KINGSIDE, 'K'),
+            (CastlingRights::WHITE_QUEENSIDE, 'Q'),
+            (CastlingRights::BLACK_KINGSIDE, 'k'),
+            (CastlingRights::BLACK_QUEENSIDE, 'q')
+        ];
+        for (right, name) in rights {
+            if self.castling_rights.contains(right) {
+                fen.push(name);
+            }
+        }
+
+        match self.en_passant {
+            Some(square) => fen.push_str(&format!(" {square}")),
+            None => fen.push_str(" -")
+        }
+        fen.push_str(&format!(" {} {}", self.halfmove_clock, self.fullmove_number));
+        fen
+    }
+
+    /// Plays a move, which must be legal in this position.
+    /// Castling is recognised as the king moving two files, and en passant as a pawn moving diagonally onto an empty square.
+    pub fn play(&mut self, m: Move) {
+        self.make_move(m);
+    }
+
+    /// Returns a copy of the board with the move played, leaving this one untouched.
+    /// Simpler than make_move and unmake_move when the original is still needed, but copies the whole board each time.
+    pub fn with_move(&self, m: Move) -> Board {
+        let mut after = self.clone();
+        after.make_move(m);
+        after
+    }
+
+    /// Plays a move like play, and returns what unmake_move needs to take it back.
+    pub fn make_move(&mut self, m: Move) -> Undo {
+        let mut undo = Undo {
+            captured: None,
+            castling_rights: self.castling_rights,
+            en_passant: self.en_passant,
+            halfmove_clock: self.halfmove_clock,
+            hash: self.hash
+        };
+        // Take out the parts of the key that might change, and put the new ones back once the move is done
+        self.hash ^= zobrist::black_to_move() ^ zobrist::castling(self.castling_rights) ^ self.en_passant_key();
+        let mover = self.side_to_move;
+        self.side_to_move = mover.opponent();
+        if mover == Color::Black {
+            self.fullmove_number += 1;
+        }
+        let previous_en_passant = self.en_passant.take();
+
+        let Move::Normal {from, to, promotion} = m else {
+            self.halfmove_clock += 1;
+            self.hash ^= zobrist::castling(self.castling_rights);
+            return undo;
+        };
+        let piece = self.remove_piece(from).expect("a legal move starts on an occupied square");
+        undo.captured = self.remove_piece(to);
+
+        match piece.kind {
+            PieceKind::Pawn => {
+                if Some(to) == previous_en_passant {
+                    // The captured pawn is beside the moving pawn, not on the square it moves to
+                    undo.captured = self.remove_piece(Square::new(to.file(), from.rank()));
+                }
+                if from.rank().abs_diff(to.rank()) == 2 {
+                    self.en_passant = Some(Square::new(from.file(), (from.rank() + to.rank()) / 2));
+                }
+            },
+            PieceKind::King => {
+                self.castling_rights.remove(CastlingRights::kingside(mover));
+                self.castling_rights.remove(CastlingRights::queenside(mover));
+                if from.file().abs_diff(to.file()) == 2 {
+                    let (rook_from, rook_to) = castling_rook_files(from, to);
+                    let rook = self.remove_piece(Square::new(rook_from, from.rank())).expect("castling needs a rook");
+                    self.put_piece(Square::new(rook_to, from.rank()), rook);
+                }
+            },
+            _ => ()
+        }
+
+        // Moving a rook off its corner, or capturing one on it, loses that castling right
+        for square in [from, to] {
+            for color in [Color::White, Color::Black] {
+                if square == Square::new(7, color.back_rank()) {
+                    self.castling_rights.remove(CastlingRights::kingside(color));
+                }
+                if square == Square::new(0, color.back_rank()) {
+                    self.castling_rights.remove(CastlingRights::queenside(color));
+                }
+            }
+        }
+
+        let kind = match promotion {
+            Some(PromotionPiece::Knight) => PieceKind::Knight,
+            Some(PromotionPiece::Bishop) => PieceKind::Bishop,
+            Some(PromotionPiece::Rook) => PieceKind::Rook,
+            Some(PromotionPiece::Queen) => PieceKind::Queen,
+            None => piece.kind
+        };
+        self.put_piece(to, Piece::new(mover, kind));
+
+        if undo.captured.is_some() || piece.kind == PieceKind::Pawn {
+            self.halfmove_clock = 0;
+        } else {
+            self.halfmove_clock += 1;
+        }
+        self.hash ^= zobrist::castling(self.castling_rights) ^ self.en_passant_key();
+        debug_assert_eq!(self.hash, self.compute_key(), "the key was updated wrongly by {m}");
+        undo
+    }
+
+    /// Takes back a move played with make_move. The move and undo record must be the ones from the last make_move on this board.
+    pub fn unmake_move(&mut self, m: Move, undo: Undo) {
+        let mover = self.side_to_move.opponent();
+        self.side_to_move = mover;
+        if mover == Color::Black {
+            self.fullmove_number -= 1;
+        }
+        self.castling_rights = undo.castling_rights;
+        self.en_passant = undo.en_passant;
+        self.halfmove_clock = undo.halfmove_clock;
+
+        let Move::Normal {from, to, promotion} = m else {
+            return;
+        };
+        let moved = self.remove_piece(to).expect("the moved piece is on the square it moved to");
+        let piece = if promotion.is_some() { Piece::new(mover, PieceKind::Pawn) } else { moved };
+        self.put_piece(from, piece);
+
+        if piece.kind == PieceKind::King && from.file().abs_diff(to.file()) == 2 {
+            let (rook_from, rook_to) = castling_rook_files(from, to);
+            let rook = self.remove_piece(Square::new(rook_to, from.rank())).expect("the castled rook is next to the king");
+            self.put_piece(Square::new(rook_from, from.rank()), rook);
+        }
+
+        if let Some(captured) = undo.captured {
+            let square = if piece.kind == PieceKind::Pawn && Some(to) == undo.en_passant {
+                Square::new(to.file(), from.rank())
+            } else {
+                to
+            };
+            self.put_piece(square, captured);
+        }
+        self.hash = undo.hash;
+    }
+
+    /// Places a piece on an empty square.
+    fn put_piece(&mut self, square: Square, piece: Piece) {
+        debug_assert!(self.squares[square.index()].is_none(), "{square} is already occupied");
+        self.squares[square.index()] = Some(piece);
+        self.kinds[piece.kind.index()] = self.kinds[piece.kind.index()].with(square);
+        self.colors[piece.color.index()] = self.colors[piece.color.index()].with(square);
+        self.hash ^= zobrist::piece(piece, square);
+    }
+
+    /// Takes whatever piece is on the square off the board.
+    fn remove_piece(&mut self, square: Square) -> Option<Piece> {
+        let piece = self.squares[square.index()].take()?;
+        self.kinds[piece.kind.index()] = self.kinds[piece.kind.index()].without(square);
+        self.colors[piece.color.index()] = self.colors[piece.color.index()].without(square);
+        self.hash ^= zobrist::piece(piece, square);
+        Some(piece)
+    }
+
+    /// The Zobrist key of the position. Positions with the same pieces, side to move, castling rights and usable en passant square have the same key.
+    /// The move counters aren't part of it, so positions reached at different points in a game can still match.
+    pub fn key(&self) -> u64 {
+        self.hash
+    }
+
+    /// Works out the key from scratch, which should always give the same as key.
+    pub fn compute_key(&self) -> u64 {
+        let mut key = zobrist::castling(self.castling_rights) ^ self.en_passant_key();
+        if self.side_to_move == Color::Black {
+            key ^= zobrist::black_to_move();
+        }
+        for (square, piece) in self.pieces() {
+            key ^= zobrist::piece(piece, square);
+        }
+        key
+    }
+
+    /// The en passant part of the key, which is only there if a pawn of the side to move stands ready to capture en passant.
+    fn en_passant_key(&self) -> u64 {
+        match self.en_passant {
+            Some(square) if !(pawn_attacks(square, self.side_to_move.opponent()) & self.pieces_of(self.side_to_move, PieceKind::Pawn)).is_empty() => {
+                zobrist::en_passant_file(square.file())
+            },
+            _ => 0
+        }
+    }
+
+    pub fn piece_at(&self, square: Square) -> Option<Piece> {
+        self.squares[square.index()]
+    }
+
+    /// Iterates over every occupied square and the piece on it, from a1 to h8.
+    pub fn pieces(&self) -> impl Iterator<Item = (Square, Piece)> + '_ {
+        self.occupied().into_iter().map(|square| (square, self.squares[square.index()].expect("occupied squares have a piece")))
+    }
+
+    /// Every square with a piece on it.
+    pub fn occupied(&self) -> Bitboard {
+        self.colors[0] | self.colors[1]
+    }
+
+    /// Every square with a piece of the given color on it.
+    pub fn color(&self, color: Color) -> Bitboard {
+        self.colors[color.index()]
+    }
+
+    /// Every square with a piece of the given kind on it, of either color.
+    pub fn kind(&self, kind: PieceKind) -> Bitboard {
+        self.kinds[kind.index()]
+    }
+
+    /// Every square with the given piece on it.
+    pub fn pieces_of(&self, color: Color, kind: PieceKind) -> Bitboard {
+        self.colors[color.index()] & self.kinds[kind.index()]
+    }
+
+    pub fn side_to_move(&self) -> Color {
+        self.side_to_move
+    }
+
+    pub fn castling_rights(&self) -> CastlingRights {
+        self.castling_rights
+    }
+
+    /// The square a pawn skipped over with a double move last turn, if any.
+    /// This is set after every double move, even if no pawn can actually capture en passant.
+    pub fn en_passant(&self) -> Option<Square> {
+        self.en_passant
+    }
+
+    /// The number of halfmoves since the last capture or pawn move.
+    pub fn halfmove_clock(&self) -> u32 {
+        self.halfmove_clock
+    }
+
+    /// Starts at 1 and goes up after each black move.
+    pub fn fullmove_number(&self) -> u32 {
+        self.fullmove_number
+    }
+
+    /// Returns true if neither side has enough material left to ever checkmate, even with the other side's help.
+    /// That's the case with only kings, a king and one knight or bishop against a king, or any number of bishops that all stand on squares of the same color.
+    pub fn has_insufficient_material(&self) -> bool {
+        let heavy = self.kind(PieceKind::Pawn) | self.kind(PieceKind::Rook) | self.kind(PieceKind::Queen);
+        if !heavy.is_empty() {
+            return false;
+        }
+        let knights = self.kind(PieceKind::Knight);
+        let bishops = self.kind(PieceKind::Bishop);
+        if (knights | bishops).count() <= 1 {
+            return true;
+        }
+        // A king always has escape squares of both colors, so bishops that only ever reach one color can't mate
+        knights.is_empty() && ((bishops & Bitboard::DARK_SQUARES).is_empty() || (bishops & Bitboard::LIGHT_SQUARES).is_empty())
+    }
+
+    pub fn king_square(&self, color: Color) -> Square {
+        self.pieces_of(color, PieceKind::King).first_square().expect("every valid board has a king of each color")
+    }
+
+    /// Returns true if the side to move is in check.
+    pub fn in_check(&self) -> bool {
+        self.is_square_attacked(self.king_square(self.side_to_move), self.side_to_move.opponent())
+    }
+
+    /// Returns true if any piece of the given color attacks the square, whether or not that piece is pinned.
+    pub fn is_square_attacked(&self, square: Square, by: Color) -> bool {
+        !self.attackers(square, by, self.occupied()).is_empty()
+    }
+
+    /// Every piece of the given color attacking the square, if the occupied squares were the ones given.
+    /// Passing a different occupancy than the board's own lets callers look through pieces that are about to move.
+    pub fn attackers(&self, square: Square, by: Color, occupied: Bitboard) -> Bitboard {
+        let queens = self.kind(PieceKind::Queen);
+        // A pawn attacks the square exactly when a pawn of the other color on the square would attack the pawn
+        let attackers = (pawn_attacks(square, by.opponent()) & self.kind(PieceKind::Pawn))
+            | (knight_attacks(square) & self.kind(PieceKind::Knight))
+            | (king_attacks(square) & self.kind(PieceKind::King))
+            | (bishop_attacks(square, occupied) & (self.kind(PieceKind::Bishop) | queens))
+            | (rook_attacks(square, occupied) & (self.kind(PieceKind::Rook) | queens));
+        attackers & self.color(by) & occupied
+    }
+}
+
+impl Default for Board {
+    fn default() -> Self {
+        Board::start_position()
+    }
+}
+
+impl std::str::FromStr for Board {
+    type Err = FenError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Board::from_fen(s)
+    }
+}
+
+/// Writes the board as a FEN string.
+impl std::fmt::Display for Board {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_fen())
+    }
+}
@@ -0,0 +1,5 @@
+//! The engines that can be plugged into the UCI front end.
+
+pub mod alphabeta;
+pub mod greedy;
+pub mod random;
@@ -0,0 +1,334 @@
+use std::time::{Duration, Instant};
+
+use crate::board::{Board, Color, PieceKind};
+use crate::eval::{material_balance, piece_value};
+use crate::game::Game;
+use crate::history::History;
+use crate::movegen::legal_moves;
+use crate::uci::{BestMove, Engine, InfoCommandData, Move, PromotionPiece, ScoreInfoData, SearchLimits, SearchSignals};
+
+/// The score for giving mate right now. Mates further away score a little less, so the search prefers the quickest one.
+const MATE: i32 = 30_000;
+/// Above any score a position can have, for the initial search window.
+const INFINITY: i32 = MATE + 1;
+/// The deepest a search goes, counting the captures searched past the nominal depth.
+const MAX_PLY: usize = 128;
+/// The deepest iterative deepening goes when nothing else stops it.
+const MAX_DEPTH: usize = 64;
+
+/// Searches every line a fixed number of moves deep with negamax and alpha-beta pruning, then follows captures until the position is quiet.
+/// The depth goes up one ply at a time, so a stop always has a finished search's best move to fall back on.
+/// Positions are judged on material alone.
+pub struct AlphaBeta {
+    game: Game
+}
+
+impl AlphaBeta {
+    pub fn new() -> AlphaBeta {
+        AlphaBeta {game: Game::default()}
+    }
+}
+
+impl Default for AlphaBeta {
+    fn default() -> Self {
+        AlphaBeta::new()
+    }
+}
+
+/// How long to spend on this move, or None if only stop or another limit ends the search.
+fn time_budget(limits: &SearchLimits, color: Color) -> Option<Duration> {
+    if limits.infinite {
+        return None;
+    }
+    if limits.move_time.is_some() {
+        return limits.move_time;
+    }
+    let (time, increment) = match color {
+        Color::White => (limits.white_time?, limits.white_increment),
+        Color::Black => (limits.black_time?, limits.black_increment)
+    };
+    // Without a time control to go by, assume the game lasts another 30 moves
+    let moves_left = limits.moves_to_go.unwrap_or(30).max(1) as u32;
+    let budget = time / moves_left + increment.unwrap_or_default() * 3 / 4;
+    // Leave a little for the move to reach the GUI
+    Some(budget.min(time.saturating_sub(Duration::from_millis(50))))
+}
+
+/// Orders captures before quiet moves, and among captures, the most valuable victim taken by the least valuable attacker first.
+/// Quiet moves score 0 and everything else scores above it.
+fn move_priority(board: &Board, m: Move) -> i32 {
+    let Move::Normal {from, to, promotion} = m else {
+        return 0;
+    };
+    let attacker = board.piece_at(from).map_or(PieceKind::Pawn, |piece| piece.kind);
+    let victim = match board.piece_at(to) {
+        Some(piece) => piece_value(piece.kind),
+        // A pawn moving to another file onto an empty square is capturing en passant
+        None if attacker == PieceKind::Pawn && from.file() != to.file() => piece_value(PieceKind::Pawn),
+        None => 0
+    };
+    let promotion = match promotion {
+        Some(PromotionPiece::Knight) => piece_value(PieceKind::Knight),
+        Some(PromotionPiece::Bishop) => piece_value(PieceKind::Bishop),
+        Some(PromotionPiece::Rook) => piece_value(PieceKind::Rook),
+        Some(PromotionPiece::Queen) => piece_value(PieceKind::Queen),
+        None => 0
+    };
+    if victim == 0 && promotion == 0 {
+        return 0;
+    }
+    (victim + promotion) * 10 - piece_value(attacker) / 10 + 100
+}
+
+fn order_moves(board: &Board, moves: &mut [Move]) {
+    moves.sort_by_cached_key(|&m| std::cmp::Reverse(move_priority(board, m)));
+}
+
+/// Turns a search score into what UCI reports, counting mates in moves rather than plies.
+fn score_info(score: i32) -> ScoreInfoData {
+    let max_ply = MAX_PLY as i32;
+    if score >= MATE - max_ply {
+        ScoreInfoData::MateInMoves(((MATE - score + 1) / 2) as isize)
+    } else if score <= -MATE + max_ply {
+        ScoreInfoData::MateInMoves(-((MATE + score) / 2) as isize)
+    } else {
+        ScoreInfoData::CentiPawns(score as isize)
+    }
+}
+
+/// The state of one call to go.
+struct Search<'a> {
+    board: Board,
+    history: History,
+    limits: &'a SearchLimits,
+    signals: &'a SearchSignals,
+    budget: Option<Duration>,
+    /// When the time budget started being used up. None while pondering, as the clock only starts on a ponder hit.
+    clock_start: Option<Instant>,
+    nodes: usize,
+    seldepth: usize,
+    /// Set once any limit runs out. Every score computed after that is meaningless and gets thrown away.
+    stopped: bool
+}
+
+impl Search<'_> {
+    fn clock_start(&mut self) -> Option<Instant> {
+        if self.clock_start.is_none() && self.signals.is_ponder_hit() {
+            self.clock_start = Some(Instant::now());
+        }
+        self.clock_start
+    }
+
+    /// The fraction of the time budget used so far, or 0 if there's no budget or the clock hasn't started.
+    fn time_used(&mut self) -> f64 {
+        match (self.budget, self.clock_start()) {
+            (Some(budget), Some(start)) => start.elapsed().as_secs_f64() / budget.as_secs_f64().max(f64::EPSILON),
+            _ => 0.0
+        }
+    }
+
+    /// Counts a node, and checks whether the search has to stop.
+    fn enter_node(&mut self, ply: usize) -> bool {
+        self.nodes += 1;
+        self.seldepth = self.seldepth.max(ply);
+        if self.limits.nodes.is_some_and(|limit| self.nodes >= limit) {
+            self.stopped = true;
+        }
+        // Checking the clock is slow next to searching a node, so only do it every so often
+        if self.nodes.is_multiple_of(1024) && (self.signals.should_stop() || self.time_used() >= 1.0) {
+            self.stopped = true;
+        }
+        self.stopped
+    }
+
+    /// Plays a move, searches the position it leads to, and takes it back. Returns the score for the side that made the move.
+    fn search_move(&mut self, m: Move, depth: usize, ply: usize, alpha: i32, beta: i32, pv: &mut Vec<Move>) -> i32 {
+        let undo = self.board.make_move(m);
+        self.history.push(self.board.key());
+        let score = -self.negamax(depth, ply + 1, -beta, -alpha, pv);
+        self.history.pop();
+        self.board.unmake_move(m, undo);
+        score
+    }
+
+    /// Searches every root move to the given depth, best first from the last iteration.
+    /// Returns the best score and line, or None if the search stopped before finishing a single move.
+    fn root(&mut self, depth: usize, moves: &[Move]) -> Option<(i32, Vec<Move>)> {
+        let mut alpha = -INFINITY;
+        let mut best = None;
+        for &m in moves {
+            let mut line = Vec::new();
+            let score = self.search_move(m, depth - 1, 0, alpha, INFINITY, &mut line);
+            if self.stopped {
+                break;
+            }
+            if score > alpha {
+                alpha = score;
+                line.insert(0, m);
+                best = Some((score, line));
+            }
+        }
+        best
+    }
+
+    /// Scores the position for the side to move, searching `depth` plies ahead. `pv` is filled with the best line found.
+    fn negamax(&mut self, depth: usize, ply: usize, mut alpha: i32, beta: i32, pv: &mut Vec<Move>) -> i32 {
+        let halfmove_clock = self.board.halfmove_clock();
+        if self.history.is_repetition(halfmove_clock) || self.board.has_insufficient_material() {
+            return 0;
+        }
+        // Mate on the move that reaches the fifty-move limit still counts
+        if halfmove_clock >= 100 {
+            return if self.board.in_check() && legal_moves(&self.board).is_empty() { -MATE + ply as i32 } else { 0 };
+        }
+        if depth == 0 {
+            return self.quiesce(ply, alpha, beta);
+        }
+        if self.enter_node(ply) {
+            return 0;
+        }
+
+        let mut moves = legal_moves(&self.board);
+        if moves.is_empty() {
+            return if self.board.in_check() { -MATE + ply as i32 } else { 0 };
+        }
+        order_moves(&self.board, &mut moves);
+        for m in moves {
+            let mut line = Vec::new();
+            let score = self.search_move(m, depth - 1, ply, alpha, beta, &mut line);
+            if self.stopped {
+                return 0;
+            }
+            if score >= beta {
+                return beta;
+            }
+            if score > alpha {
+                alpha = score;
+                pv.clear();
+                pv.push(m);
+                pv.append(&mut line);
+            }
+        }
+        alpha
+    }
+
+    /// Keeps searching captures past the nominal depth, so the search never stops halfway through an exchange.
+    /// The side to move can always decline to capture, unless it's in check, in which case every way out is searched.
+    fn quiesce(&mut self, ply: usize, mut alpha: i32, beta: i32) -> i32 {
+        if self.enter_node(ply) {
+            return 0;
+        }
+        if ply >= MAX_PLY {
+            return material_balance(&self.board);
+        }
+        let in_check = self.board.in_check();
+        if !in_check {
+            let stand_pat = material_balance(&self.board);
+            if stand_pat >= beta {
+                return beta;
+            }
+            alpha = alpha.max(stand_pat);
+        }
+
+        let mut moves = legal_moves(&self.board);
+        if in_check && moves.is_empty() {
+            return -MATE + ply as i32;
+        }
+        if !in_check {
+            moves.retain(|&m| move_priority(&self.board, m) > 0);
+        }
+        order_moves(&self.board, &mut moves);
+        for m in moves {
+            let undo = self.board.make_move(m);
+            let score = -self.quiesce(ply + 1, -beta, -alpha);
+            self.board.unmake_move(m, undo);
+            if self.stopped {
+                return 0;
+            }
+            if score >= beta {
+                return beta;
+            }
+            alpha = alpha.max(score);
+        }
+        alpha
+    }
+}
+
+impl Engine for AlphaBeta {
+    fn name(&self) -> String {
+        "RustyChess Alpha-Beta".to_string()
+    }
+
+    fn author(&self) -> String {
+        "LilyIsTrans".to_string()
+    }
+
+    fn set_position(&mut self, game: Game) {
+        self.game = game;
+    }
+
+    fn go(&mut self, limits: &SearchLimits, signals: &SearchSignals, info: &mut dyn FnMut(Vec<InfoCommandData>)) -> BestMove {
+        let started = Instant::now();
+        let board = self.game.board().clone();
+        let mut root_moves = legal_moves(&board);
+        if !limits.search_moves.is_empty() {
+            root_moves.retain(|m| limits.search_moves.contains(m));
+        }
+        order_moves(&board, &mut root_moves);
+        let mut best = BestMove {selected_move: root_moves.first().copied().unwrap_or(Move::Null), ponder: None};
+
+        let mut search = Search {
+            history: History::from_game(&self.game),
+            budget: time_budget(limits, board.side_to_move()),
+            clock_start: (!limits.ponder).then_some(started),
+            board,
+            limits,
+            signals,
+            nodes: 0,
+            seldepth: 0,
+            stopped: false
+        };
+        // A mate in n moves is at most 2n - 1 plies away
+        let max_depth = [limits.depth, limits.mate.map(|moves| (2 * moves).saturating_sub(1)), Some(MAX_DEPTH)].into_iter().flatten().min().unwrap_or(MAX_DEPTH).max(1);
+
+        if !root_moves.is_empty() {
+            for depth in 1..=max_depth {
+                search.seldepth = 0;
+                let Some((score, line)) = search.root(depth, &root_moves) else {
+                    break;
+                };
+                best = BestMove {selected_move: line[0], ponder: line.get(1).copied()};
+                // Search the best move first next time, since it's probably still the best
+                if let Some(index) = root_moves.iter().position(|&m| m == line[0]) {
+                    root_moves[..=index].rotate_right(1);
+                }
+                if search.stopped {
+                    break;
+                }
+
+                let elapsed = started.elapsed();
+                info(vec![
+                    InfoCommandData::Depth(depth),
+                    InfoCommandData::SelectiveDepth(search.seldepth),
+                    InfoCommandData::Score(score_info(score)),
+                    InfoCommandData::NodesSearched(search.nodes),
+                    InfoCommandData::NodesPerSecond((search.nodes as f64 / elapsed.as_secs_f64().max(0.001)) as usize),
+                    InfoCommandData::TimeSpentSearching(elapsed.as_millis() as usize),
+                    InfoCommandData::PrincipleVariation(line)
+                ]);
+
+                // Once a mate is found, searching deeper can't change the result
+                if score.abs() >= MATE - MAX_PLY as i32 {
+                    break;
+                }
+                // The next iteration takes several times as long as this one, so don't start one that won't finish
+                if search.time_used() >= 0.5 {
+                    break;
+                }
+            }
+        }
+
+        signals.wait_until_released(limits);
+        best
+    }
+}
@@ -0,0 +1,83 @@
+use std::time::Instant;
+
+use crate::board::Board;
+use crate::eval::material_balance;
+use crate::game::Game;
+use crate::movegen::legal_moves;
+use crate::rng::Rng;
+use crate::uci::{BestMove, Engine, InfoCommandData, Move, ScoreInfoData, SearchLimits, SearchSignals};
+
+/// Looks one move ahead: mates if it can, and otherwise grabs as much material as possible, choosing randomly between equally good moves.
+/// It never considers the reply, so it'll happily take a defended pawn with its queen.
+pub struct GreedyMover {
+    board: Board,
+    rng: Rng
+}
+
+impl GreedyMover {
+    /// The same generator always breaks ties the same way, which makes games reproducible.
+    pub fn new(rng: Rng) -> GreedyMover {
+        GreedyMover {board: Board::start_position(), rng}
+    }
+}
+
+impl Default for GreedyMover {
+    fn default() -> Self {
+        GreedyMover::new(Rng::from_entropy())
+    }
+}
+
+/// Mate beats any amount of material. Stalemate is a draw however far ahead we are, so it's scored as even.
+const MATE: i32 = i32::MAX;
+
+/// How good the position after the move is for the side making it.
+fn score_move(board: &Board, m: Move) -> i32 {
+    let after = board.with_move(m);
+    if legal_moves(&after).is_empty() {
+        return if after.in_check() { MATE } else { 0 };
+    }
+    -material_balance(&after)
+}
+
+impl Engine for GreedyMover {
+    fn name(&self) -> String {
+        "RustyChess Greedy Mover".to_string()
+    }
+
+    fn author(&self) -> String {
+        "LilyIsTrans".to_string()
+    }
+
+    fn set_position(&mut self, game: Game) {
+        self.board = game.board().clone();
+    }
+
+    fn go(&mut self, limits: &SearchLimits, signals: &SearchSignals, info: &mut dyn FnMut(Vec<InfoCommandData>)) -> BestMove {
+        let started = Instant::now();
+        let mut moves = legal_moves(&self.board);
+        if !limits.search_moves.is_empty() {
+            moves.retain(|m| limits.search_moves.contains(m));
+        }
+        let scores: Vec<i32> = moves.iter().map(|&m| score_move(&self.board, m)).collect();
+        let best_score = scores.iter().copied().max();
+        let best: Vec<Move> = moves.iter().zip(&scores).filter(|&(_, &score)| Some(score) == best_score).map(|(&m, _)| m).collect();
+        let selected_move = self.rng.choose(&best).copied().unwrap_or(Move::Null);
+
+        if let Some(best_score) = best_score {
+            let score = match best_score {
+                MATE => ScoreInfoData::MateInMoves(1),
+                score => ScoreInfoData::CentiPawns(score as isize)
+            };
+            info(vec![
+                InfoCommandData::Depth(1),
+                InfoCommandData::Score(score),
+                InfoCommandData::NodesSearched(moves.len()),
+                InfoCommandData::TimeSpentSearching(started.elapsed().as_millis() as usize),
+                InfoCommandData::PrincipleVariation(vec![selected_move])
+            ]);
+        }
+
+        signals.wait_until_released(limits);
+        BestMove {selected_move, ponder: None}
+    }
+}
@@ -0,0 +1,50 @@
+use crate::board::Board;
+use crate::game::Game;
+use crate::movegen::legal_moves;
+use crate::rng::Rng;
+use crate::uci::{BestMove, Engine, InfoCommandData, Move, SearchLimits, SearchSignals};
+
+/// The baseline engine: plays a uniformly random legal move, and nothing else.
+pub struct RandomMover {
+    board: Board,
+    rng: Rng
+}
+
+impl RandomMover {
+    /// The same generator always produces the same games, which makes them reproducible.
+    pub fn new(rng: Rng) -> RandomMover {
+        RandomMover {board: Board::start_position(), rng}
+    }
+}
+
+impl Default for RandomMover {
+    fn default() -> Self {
+        RandomMover::new(Rng::from_entropy())
+    }
+}
+
+impl Engine for RandomMover {
+    fn name(&self) -> String {
+        "RustyChess Random Mover".to_string()
+    }
+
+    fn author(&self) -> String {
+        "LilyIsTrans".to_string()
+    }
+
+    fn set_position(&mut self, game: Game) {
+        self.board = game.board().clone();
+    }
+
+    fn go(&mut self, limits: &SearchLimits, signals: &SearchSignals, _info: &mut dyn FnMut(Vec<InfoCommandData>)) -> BestMove {
+        let mut moves = legal_moves(&self.board);
+        if !limits.search_moves.is_empty() {
+            moves.retain(|m| limits.search_moves.contains(m));
+        }
+        // With no legal moves the game is already over, and the null move is the only honest answer
+        let selected_move = self.rng.choose(&moves).copied().unwrap_or(Move::Null);
+
+        signals.wait_until_released(limits);
+        BestMove {selected_move, ponder: None}
+    }
+}
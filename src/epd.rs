@@ -0,0 +1,173 @@
+//! Reading EPD (Extended Position Description), the format test suites like Win At Chess are written in.
+//! Each line is the first four fields of a FEN followed by operations, such as `bm Qxf7+; id "WAC.001";`.
+
+use crate::board::{Board, FenError};
+use crate::san::{parse_san, SanError};
+use crate::uci::Move;
+
+/// Describes why a line of EPD couldn't be read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EpdError {
+    /// The line didn't start with four FEN fields.
+    MissingFields,
+    InvalidFen(FenError),
+    /// A quoted operand was never closed.
+    UnterminatedString,
+    /// An operation had operands but no opcode, or the opcode wasn't a valid identifier. Holds the operation's text.
+    InvalidOperation(String)
+}
+
+impl std::fmt::Display for EpdError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EpdError::MissingFields => write!(f, "expected the first four fields of a FEN"),
+            EpdError::InvalidFen(error) => write!(f, "invalid position: {error}"),
+            EpdError::UnterminatedString => write!(f, "a quoted string is never closed"),
+            EpdError::InvalidOperation(operation) => write!(f, "\"{operation}\" is not a valid operation")
+        }
+    }
+}
+
+impl std::error::Error for EpdError {}
+
+impl From<FenError> for EpdError {
+    fn from(error: FenError) -> Self {
+        EpdError::InvalidFen(error)
+    }
+}
+
+/// A position with its operations. Opcodes are kept exactly as written, and quoted operands have their quotes removed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Epd {
+    /// The halfmove clock and fullmove number come from the hmvc and fmvn operations, or default to 0 and 1.
+    pub board: Board,
+    pub operations: Vec<(String, Vec<String>)>
+}
+
+impl Epd {
+    /// The operands of the first operation with the given opcode, if there is one.
+    pub fn operation(&self, opcode: &str) -> Option<&[String]> {
+        self.operations.iter().find(|(name, _)| name == opcode).map(|(_, operands)| operands.as_slice())
+    }
+
+    /// The position's name, from the "id" operation.
+    pub fn id(&self) -> Option<&str> {
+        self.operation("id").and_then(|operands| operands.first()).map(String::as_str)
+    }
+
+    /// One of the comments c0 to c9.
+    pub fn comment(&self, number: u8) -> Option<&str> {
+        self.operation(&format!("c{number}")).and_then(|operands| operands.first()).map(String::as_str)
+    }
+
+    /// The moves the "bm" operation says are best, turned from SAN into moves. Empty if there's no "bm".
+    pub fn best_moves(&self) -> Result<Vec<Move>, SanError> {
+        self.moves("bm")
+    }
+
+    /// The moves the "am" operation says to avoid. Empty if there's no "am".
+    pub fn avoid_moves(&self) -> Result<Vec<Move>, SanError> {
+        self.moves("am")
+    }
+
+    /// The number of moves to mate, from the "dm" operation.
+    pub fn direct_mate(&self) -> Option<usize> {
+        self.operation("dm").and_then(|operands| operands.first()).and_then(|moves| moves.parse().ok())
+    }
+
+    fn moves(&self, opcode: &str) -> Result<Vec<Move>, SanError> {
+        self.operation(opcode).unwrap_or_default().iter().map(|san| parse_san(&self.board, san)).collect()
+    }
+}
+
+/// Splits the operations part of a line into separate operations, keeping semicolons inside quoted strings.
+fn parse_operations(text: &str) -> Result<Vec<(String, Vec<String>)>, EpdError> {
+    let mut operations = Vec::new();
+    let mut words = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            ';' => {
+                if let Some(operation) = finish_operation(std::mem::take(&mut words))? {
+                    operations.push(operation);
+                }
+            },
+            '"' => {
+                let mut string = String::new();
+                loop {
+                    match chars.next() {
+                        None => return Err(EpdError::UnterminatedString),
+                        Some('"') => break,
+                        Some(c) => string.push(c)
+                    }
+                }
+                words.push(string);
+            },
+            _ if c.is_whitespace() => (),
+            _ => {
+                let mut word = c.to_string();
+                while let Some(&next) = chars.peek() {
+                    if next.is_whitespace() || next == ';' || next == '"' {
+                        break;
+                    }
+                    word.push(next);
+                    chars.next();
+                }
+                words.push(word);
+            }
+        }
+    }
+    // The last operation should end with a semicolon too, but plenty of files leave it off
+    if let Some(operation) = finish_operation(words)? {
+        operations.push(operation);
+    }
+    Ok(operations)
+}
+
+fn finish_operation(mut words: Vec<String>) -> Result<Option<(String, Vec<String>)>, EpdError> {
+    if words.is_empty() {
+        return Ok(None);
+    }
+    let opcode = words.remove(0);
+    let valid = opcode.chars().next().is_some_and(|c| c.is_ascii_alphabetic()) && opcode.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid {
+        return Err(EpdError::InvalidOperation(std::iter::once(opcode).chain(words).collect::<Vec<_>>().join(" ")));
+    }
+    Ok(Some((opcode, words)))
+}
+
+impl std::str::FromStr for Epd {
+    type Err = EpdError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let line = line.trim();
+        let mut fields = Vec::new();
+        let mut rest = line;
+        for _ in 0..4 {
+            let (field, remainder) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+            if field.is_empty() {
+                return Err(EpdError::MissingFields);
+            }
+            fields.push(field);
+            rest = remainder.trim_start();
+        }
+        let operations = parse_operations(rest)?;
+
+        let clock = |opcode: &str, default: &'static str| {
+            operations.iter().find(|(name, _)| name == opcode)
+                .and_then(|(_, operands)| operands.first())
+                .map_or(default.to_string(), String::clone)
+        };
+        let fen = format!("{} {} {}", fields.join(" "), clock("hmvc", "0"), clock("fmvn", "1"));
+        Ok(Epd {board: Board::from_fen(&fen)?, operations})
+    }
+}
+
+/// Reads every position in an EPD file. Blank lines and lines starting with # are skipped.
+/// Errors are returned with the line number they were on, counting from 1.
+pub fn read_epd(text: &str) -> Result<Vec<Epd>, (usize, EpdError)> {
+    text.lines().enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .map(|(index, line)| line.parse().map_err(|error| (index + 1, error)))
+        .collect()
+}
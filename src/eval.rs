@@ -0,0 +1,27 @@
+//! Static evaluation: how good a position looks without searching any further.
+//! Scores are in centipawns, from the point of view of the side to move, so the same code works for both sides in a negamax search.
+
+use crate::board::{Board, Color, PieceKind};
+
+/// The traditional value of a piece, in centipawns. The king is never traded, so it's worth nothing.
+pub const fn piece_value(kind: PieceKind) -> i32 {
+    match kind {
+        PieceKind::Pawn => 100,
+        PieceKind::Knight => 300,
+        PieceKind::Bishop => 300,
+        PieceKind::Rook => 500,
+        PieceKind::Queen => 900,
+        PieceKind::King => 0
+    }
+}
+
+/// The total value of one side's pieces.
+pub fn material(board: &Board, color: Color) -> i32 {
+    PieceKind::ALL.iter().map(|&kind| board.pieces_of(color, kind).count() as i32 * piece_value(kind)).sum()
+}
+
+/// How much more material the side to move has than its opponent.
+pub fn material_balance(board: &Board) -> i32 {
+    let us = board.side_to_move();
+    material(board, us) - material(board, us.opponent())
+}
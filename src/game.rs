@@ -0,0 +1,198 @@
+use crate::board::{Board, Color};
+use crate::history::History;
+use crate::movegen::{is_legal, legal_moves};
+use crate::uci::Move;
+
+/// Returned when trying to play a move that isn't legal in the current position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IllegalMove(pub Move);
+
+impl std::fmt::Display for IllegalMove {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} is not a legal move", self.0)
+    }
+}
+
+impl std::error::Error for IllegalMove {}
+
+/// Why a game was drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DrawReason {
+    /// The side to move has no legal moves but isn't in check.
+    Stalemate,
+    InsufficientMaterial,
+    FivefoldRepetition,
+    SeventyFiveMoveRule,
+    /// Only a draw if a player claims it.
+    ThreefoldRepetition,
+    /// Only a draw if a player claims it.
+    FiftyMoveRule
+}
+
+impl DrawReason {
+    /// Returns true if the game ends by itself, false if a player has to claim the draw.
+    pub fn is_automatic(self) -> bool {
+        !matches!(self, DrawReason::ThreefoldRepetition | DrawReason::FiftyMoveRule)
+    }
+}
+
+impl std::fmt::Display for DrawReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DrawReason::Stalemate => write!(f, "stalemate"),
+            DrawReason::InsufficientMaterial => write!(f, "insufficient material"),
+            DrawReason::FivefoldRepetition => write!(f, "fivefold repetition"),
+            DrawReason::SeventyFiveMoveRule => write!(f, "75 move rule"),
+            DrawReason::ThreefoldRepetition => write!(f, "threefold repetition"),
+            DrawReason::FiftyMoveRule => write!(f, "50 move rule")
+        }
+    }
+}
+
+/// How a finished game ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Outcome {
+    Checkmate {winner: Color},
+    Draw(DrawReason)
+}
+
+impl Outcome {
+    /// The side that won, or None for a draw.
+    pub fn winner(self) -> Option<Color> {
+        match self {
+            Outcome::Checkmate {winner} => Some(winner),
+            Outcome::Draw(_) => None
+        }
+    }
+}
+
+impl std::fmt::Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Checkmate {winner: Color::White} => write!(f, "white wins by checkmate"),
+            Outcome::Checkmate {winner: Color::Black} => write!(f, "black wins by checkmate"),
+            Outcome::Draw(reason) => write!(f, "draw by {reason}")
+        }
+    }
+}
+
+/// Whether a game is still going, and if not, how it ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GameState {
+    Ongoing,
+    Over(Outcome)
+}
+
+/// A position together with how it was reached.
+/// Rules like threefold repetition depend on the earlier positions, so engines need more than the current board.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    moves: Vec<Move>,
+    /// Every position reached so far, starting with the initial one and ending with the current one.
+    positions: Vec<Board>
+}
+
+impl Game {
+    pub fn new(start: Board) -> Game {
+        Game {moves: Vec::new(), positions: vec![start]}
+    }
+
+    /// The position the game started from.
+    pub fn start(&self) -> &Board {
+        &self.positions[0]
+    }
+
+    /// The current position.
+    pub fn board(&self) -> &Board {
+        self.positions.last().expect("a game always has its starting position")
+    }
+
+    /// The moves played since the start, in order.
+    pub fn moves(&self) -> &[Move] {
+        &self.moves
+    }
+
+    /// Every position reached, oldest first. The last one is the current position.
+    pub fn positions(&self) -> &[Board] {
+        &self.positions
+    }
+
+    /// How many times the current position has occurred in the game, counting this time.
+    /// Positions count as the same if the same side is to move with the same pieces, castling rights and usable en passant square.
+    pub fn repetitions(&self) -> usize {
+        History::from_game(self).occurrences(self.board().halfmove_clock())
+    }
+
+    /// Either player may claim a draw once the same position has occurred three times.
+    pub fn is_threefold_repetition(&self) -> bool {
+        self.repetitions() >= 3
+    }
+
+    /// The game is drawn automatically once the same position has occurred five times.
+    pub fn is_fivefold_repetition(&self) -> bool {
+        self.repetitions() >= 5
+    }
+
+    /// Either player may claim a draw once 50 moves each have been played without a capture or pawn move.
+    pub fn is_fifty_move_rule(&self) -> bool {
+        self.board().halfmove_clock() >= 100
+    }
+
+    /// The game is drawn automatically after 75 moves each without a capture or pawn move, unless the last move gave checkmate.
+    pub fn is_seventy_five_move_rule(&self) -> bool {
+        self.board().halfmove_clock() >= 150
+    }
+
+    /// Whether the game is over, and why.
+    /// Draws that have to be claimed count as over, as if the player who could claim always did. Use DrawReason::is_automatic to tell them apart.
+    /// When several reasons apply at once, checkmate wins over everything else, and automatic draws are reported before claimable ones.
+    pub fn state(&self) -> GameState {
+        let board = self.board();
+        if legal_moves(board).is_empty() {
+            let outcome = if board.in_check() {
+                Outcome::Checkmate {winner: board.side_to_move().opponent()}
+            } else {
+                Outcome::Draw(DrawReason::Stalemate)
+            };
+            return GameState::Over(outcome);
+        }
+
+        let repetitions = self.repetitions();
+        let draws = [
+            (repetitions >= 5, DrawReason::FivefoldRepetition),
+            (self.is_seventy_five_move_rule(), DrawReason::SeventyFiveMoveRule),
+            (board.has_insufficient_material(), DrawReason::InsufficientMaterial),
+            (repetitions >= 3, DrawReason::ThreefoldRepetition),
+            (self.is_fifty_move_rule(), DrawReason::FiftyMoveRule)
+        ];
+        match draws.into_iter().find(|(applies, _)| *applies) {
+            Some((_, reason)) => GameState::Over(Outcome::Draw(reason)),
+            None => GameState::Ongoing
+        }
+    }
+
+    /// How the game ended, or None if it's still going. See state for how draws are counted.
+    pub fn outcome(&self) -> Option<Outcome> {
+        match self.state() {
+            GameState::Ongoing => None,
+            GameState::Over(outcome) => Some(outcome)
+        }
+    }
+
+    /// Plays a move if it's legal, and leaves the game untouched if it isn't.
+    pub fn play(&mut self, m: Move) -> Result<(), IllegalMove> {
+        if !is_legal(self.board(), m) {
+            return Err(IllegalMove(m));
+        }
+        let board = self.board().with_move(m);
+        self.moves.push(m);
+        self.positions.push(board);
+        Ok(())
+    }
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Game::new(Board::start_position())
+    }
+}
@@ -0,0 +1,56 @@
+//! Detecting repeated positions from their Zobrist keys.
+
+use crate::game::Game;
+
+/// The keys of every position reached so far, oldest first, for finding repetitions cheaply.
+/// A search pushes a key for each move it makes and pops it when taking the move back, so the history covers both the game and the line being searched.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct History {
+    keys: Vec<u64>
+}
+
+impl History {
+    pub fn new() -> History {
+        History::default()
+    }
+
+    /// Starts with every position of the game so far, ending with the current one.
+    pub fn from_game(game: &Game) -> History {
+        History {keys: game.positions().iter().map(|board| board.key()).collect()}
+    }
+
+    /// Adds the key of the position just reached.
+    pub fn push(&mut self, key: u64) {
+        self.keys.push(key);
+    }
+
+    /// Removes the latest key, when the move that reached it is taken back.
+    pub fn pop(&mut self) -> Option<u64> {
+        self.keys.pop()
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// How many times the latest position has occurred, counting itself.
+    /// `halfmove_clock` is the latest position's, and limits how far back to look: nothing before a capture or pawn move can ever come back.
+    pub fn occurrences(&self, halfmove_clock: u32) -> usize {
+        let Some((&latest, earlier)) = self.keys.split_last() else {
+            return 0;
+        };
+        // The same side has to be to move, so only every other position can match
+        let window = (halfmove_clock as usize).min(earlier.len());
+        1 + earlier[earlier.len() - window..].iter().rev().skip(1).step_by(2).filter(|&&key| key == latest).count()
+    }
+
+    /// Returns true if the latest position has occurred before.
+    /// Searches treat this as a draw: if repeating was good for one side, they can just repeat again, so there's no point searching further.
+    pub fn is_repetition(&self, halfmove_clock: u32) -> bool {
+        self.occurrences(halfmove_clock) >= 2
+    }
+}
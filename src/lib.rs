@@ -1,22 +1,226 @@
-use std::thread;
-use std::sync::mpsc;
+pub mod attacks;
+pub mod bitboard;
+pub mod board;
+pub mod engines;
+pub mod epd;
+pub mod eval;
+pub mod game;
+pub mod history;
+pub mod movegen;
+pub mod perft;
+pub mod pgn;
+pub mod polyglot;
+pub mod rng;
+pub mod san;
+pub mod zobrist;
 
 pub mod uci {
-    enum Move {
-        // TODO: Implement
+    use std::io::{BufRead, Write};
+    use std::sync::mpsc;
+    use std::thread;
+
+    /// A square on the board, stored as its index counting from a1 (0) along each rank up to h8 (63).
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+    pub struct Square(u8);
+
+    impl Square {
+        /// Files and ranks both count from 0, so a1 is (0, 0) and h8 is (7, 7).
+        /// Panics if either is off the board.
+        pub const fn new(file: u8, rank: u8) -> Square {
+            assert!(file < 8 && rank < 8, "square off the board");
+            Square(rank * 8 + file)
+        }
+
+        /// Returns None if the index is off the board.
+        pub const fn from_index(index: u8) -> Option<Square> {
+            if index < 64 {
+                Some(Square(index))
+            } else {
+                None
+            }
+        }
+
+        pub const fn index(self) -> usize {
+            self.0 as usize
+        }
+
+        pub const fn file(self) -> u8 {
+            self.0 % 8
+        }
+
+        pub const fn rank(self) -> u8 {
+            self.0 / 8
+        }
+    }
+
+    /// Describes why a string couldn't be turned into a Square.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum SquareParseError {
+        /// A square is always exactly 2 characters. Holds the offending string.
+        WrongLength(String),
+        /// The file wasn't one of a-h.
+        InvalidFile(char),
+        /// The rank wasn't one of 1-8.
+        InvalidRank(char)
+    }
+
+    impl std::fmt::Display for SquareParseError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                SquareParseError::WrongLength(string) => write!(f, "\"{string}\" is not 2 characters long"),
+                SquareParseError::InvalidFile(file) => write!(f, "'{file}' is not a file"),
+                SquareParseError::InvalidRank(rank) => write!(f, "'{rank}' is not a rank")
+            }
+        }
+    }
+
+    impl std::error::Error for SquareParseError {}
+
+    impl std::str::FromStr for Square {
+        type Err = SquareParseError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let mut chars = s.chars();
+            let (Some(file), Some(rank), None) = (chars.next(), chars.next(), chars.next()) else {
+                return Err(SquareParseError::WrongLength(s.to_string()));
+            };
+            if !('a'..='h').contains(&file) {
+                return Err(SquareParseError::InvalidFile(file));
+            }
+            if !('1'..='8').contains(&rank) {
+                return Err(SquareParseError::InvalidRank(rank));
+            }
+            Ok(Square::new(file as u8 - b'a', rank as u8 - b'1'))
+        }
+    }
+
+    impl std::fmt::Display for Square {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}{}", (b'a' + self.file()) as char, (b'1' + self.rank()) as char)
+        }
+    }
+
+    /// The pieces a pawn can promote to.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum PromotionPiece {
+        Knight,
+        Bishop,
+        Rook,
+        Queen
+    }
+
+    impl PromotionPiece {
+        /// The lowercase letter used for the piece in long algebraic notation.
+        pub const fn to_char(self) -> char {
+            match self {
+                PromotionPiece::Knight => 'n',
+                PromotionPiece::Bishop => 'b',
+                PromotionPiece::Rook => 'r',
+                PromotionPiece::Queen => 'q'
+            }
+        }
+
+        pub const fn from_char(c: char) -> Option<PromotionPiece> {
+            match c {
+                'n' => Some(PromotionPiece::Knight),
+                'b' => Some(PromotionPiece::Bishop),
+                'r' => Some(PromotionPiece::Rook),
+                'q' => Some(PromotionPiece::Queen),
+                _ => None
+            }
+        }
+    }
+
+    /// A move in the form UCI uses: where the piece starts, where it ends up, and what it promotes to if it's a pawn reaching the last rank.
+    /// Castling is written as the king's move, so white castling kingside is e1g1.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum Move {
+        /// Represents the "0000" null move, which passes the turn without moving anything.
+        Null,
+        /// Any other move.
+        Normal {from: Square, to: Square, promotion: Option<PromotionPiece>}
+    }
+
+    impl Move {
+        pub const fn new(from: Square, to: Square) -> Move {
+            Move::Normal {from, to, promotion: None}
+        }
+
+        pub const fn with_promotion(from: Square, to: Square, piece: PromotionPiece) -> Move {
+            Move::Normal {from, to, promotion: Some(piece)}
+        }
+    }
+
+    /// Describes why a string couldn't be turned into a Move.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum MoveParseError {
+        /// A move is always 4 characters, or 5 with a promotion. Holds the offending string.
+        WrongLength(String),
+        /// Either the origin or the destination wasn't a real square.
+        InvalidSquare(SquareParseError),
+        /// The 5th character wasn't one of n, b, r or q.
+        InvalidPromotion(char)
+    }
+
+    impl std::fmt::Display for MoveParseError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                MoveParseError::WrongLength(string) => write!(f, "\"{string}\" is not 4 or 5 characters long"),
+                MoveParseError::InvalidSquare(error) => write!(f, "invalid square: {error}"),
+                MoveParseError::InvalidPromotion(piece) => write!(f, "'{piece}' is not a piece a pawn can promote to")
+            }
+        }
+    }
+
+    impl std::error::Error for MoveParseError {}
+
+    impl From<SquareParseError> for MoveParseError {
+        fn from(error: SquareParseError) -> Self {
+            MoveParseError::InvalidSquare(error)
+        }
+    }
+
+    impl std::str::FromStr for Move {
+        type Err = MoveParseError;
+
+        /// Parses long algebraic notation, such as "e2e4", "e7e8q" or "0000".
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            if s == "0000" {
+                return Ok(Move::Null);
+            }
+            if !s.is_ascii() || !(4..=5).contains(&s.len()) {
+                return Err(MoveParseError::WrongLength(s.to_string()));
+            }
+            let from = s[0..2].parse()?;
+            let to = s[2..4].parse()?;
+            let promotion = match s[4..].chars().next() {
+                Some(c) => Some(PromotionPiece::from_char(c).ok_or(MoveParseError::InvalidPromotion(c))?),
+                None => None
+            };
+            Ok(Move::Normal {from, to, promotion})
+        }
     }
     
-    enum Position {
+    /// Where a position starts from, before any moves are played.
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum PositionBase {
         /// A FEN string
         Fen(String),
         /// The normal chess starting position
-        StartPosition,
-        /// A list of moves since the start of the game
-        MoveList(Vec<Move>)
+        StartPosition
+    }
+
+    /// The data of the "position" command: a starting point, and the moves played from there.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Position {
+        pub base: PositionBase,
+        /// A list of moves since the base position
+        pub moves: Vec<Move>
     }
     
     /// Literally a whole enum for just the "go" command
-    enum GoCommand {
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum GoCommand {
         /// Represents subcommand "searchmoves".
         /// The engine should restrict it's search to only these moves from the current position.
         SearchMoves(Vec<Move>),
@@ -56,8 +260,125 @@ pub mod uci {
     
     }
 
+    /// All the subcommands of one "go" command gathered into one place, so engines don't each have to interpret a list of GoCommands.
+    /// A limit that wasn't given is None. If nothing at all limits the search, it should go on until told to stop.
+    #[derive(Debug, Clone, PartialEq, Eq, Default)]
+    pub struct SearchLimits {
+        /// Only these moves should be considered. Empty means every legal move.
+        pub search_moves: Vec<Move>,
+        pub ponder: bool,
+        pub white_time: Option<std::time::Duration>,
+        pub black_time: Option<std::time::Duration>,
+        pub white_increment: Option<std::time::Duration>,
+        pub black_increment: Option<std::time::Duration>,
+        pub moves_to_go: Option<usize>,
+        pub depth: Option<usize>,
+        pub nodes: Option<usize>,
+        pub mate: Option<usize>,
+        pub move_time: Option<std::time::Duration>,
+        pub infinite: bool
+    }
+
+    /// Describes why a "go" command's subcommands don't make sense together.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum LimitsError {
+        /// The same subcommand was given more than once.
+        Duplicate(&'static str),
+        /// Two subcommands contradict each other, like "infinite" and "movetime".
+        Conflict(&'static str, &'static str)
+    }
+
+    impl std::fmt::Display for LimitsError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                LimitsError::Duplicate(subcommand) => write!(f, "\"{subcommand}\" was given more than once"),
+                LimitsError::Conflict(first, second) => write!(f, "\"{first}\" can't be combined with \"{second}\"")
+            }
+        }
+    }
+
+    impl std::error::Error for LimitsError {}
+
+    impl GoCommand {
+        /// The keyword this subcommand is written as.
+        pub fn keyword(&self) -> &'static str {
+            match self {
+                GoCommand::SearchMoves(_) => "searchmoves",
+                GoCommand::Ponder => "ponder",
+                GoCommand::WhiteClockLeft(_) => "wtime",
+                GoCommand::BlackClockLeft(_) => "btime",
+                GoCommand::WhiteIncrement(_) => "winc",
+                GoCommand::BlackIncrement(_) => "binc",
+                GoCommand::MovesToGo(_) => "movestogo",
+                GoCommand::MaxSearchDepth(_) => "depth",
+                GoCommand::MaxSearchNodes(_) => "nodes",
+                GoCommand::Mate(_) => "mate",
+                GoCommand::TargetSearchTime(_) => "movetime",
+                GoCommand::InfiniteSearch => "infinite"
+            }
+        }
+    }
+
+    impl SearchLimits {
+        /// Gathers the subcommands of a "go" command, rejecting repeated subcommands and limits that contradict "infinite".
+        pub fn from_go(subcommands: &[GoCommand]) -> Result<SearchLimits, LimitsError> {
+            let mut limits = SearchLimits::default();
+            let mut seen: Vec<&'static str> = Vec::new();
+            for subcommand in subcommands {
+                let keyword = subcommand.keyword();
+                if seen.contains(&keyword) {
+                    return Err(LimitsError::Duplicate(keyword));
+                }
+                seen.push(keyword);
+                let milliseconds = |ms: &usize| Some(std::time::Duration::from_millis(*ms as u64));
+                match subcommand {
+                    GoCommand::SearchMoves(moves) => limits.search_moves = moves.clone(),
+                    GoCommand::Ponder => limits.ponder = true,
+                    GoCommand::WhiteClockLeft(ms) => limits.white_time = milliseconds(ms),
+                    GoCommand::BlackClockLeft(ms) => limits.black_time = milliseconds(ms),
+                    GoCommand::WhiteIncrement(ms) => limits.white_increment = milliseconds(ms),
+                    GoCommand::BlackIncrement(ms) => limits.black_increment = milliseconds(ms),
+                    GoCommand::MovesToGo(moves) => limits.moves_to_go = Some(*moves),
+                    GoCommand::MaxSearchDepth(depth) => limits.depth = Some(*depth),
+                    GoCommand::MaxSearchNodes(nodes) => limits.nodes = Some(*nodes),
+                    GoCommand::Mate(moves) => limits.mate = Some(*moves),
+                    GoCommand::TargetSearchTime(ms) => limits.move_time = milliseconds(ms),
+                    GoCommand::InfiniteSearch => limits.infinite = true
+                }
+            }
+
+            // "infinite" means search until "stop", so any other limit on the search contradicts it
+            if limits.infinite {
+                for keyword in ["movetime", "depth", "nodes", "mate", "wtime", "btime", "movestogo"] {
+                    if seen.contains(&keyword) {
+                        return Err(LimitsError::Conflict("infinite", keyword));
+                    }
+                }
+            }
+            Ok(limits)
+        }
+
+        /// How long the given side has left on its clock, if the GUI said.
+        pub fn time_left(&self, color: crate::board::Color) -> Option<std::time::Duration> {
+            match color {
+                crate::board::Color::White => self.white_time,
+                crate::board::Color::Black => self.black_time
+            }
+        }
+
+        /// How much time the given side gets back after each move. Zero if the GUI didn't say.
+        pub fn increment(&self, color: crate::board::Color) -> std::time::Duration {
+            let increment = match color {
+                crate::board::Color::White => self.white_increment,
+                crate::board::Color::Black => self.black_increment
+            };
+            increment.unwrap_or_default()
+        }
+    }
+
     /// Represents commands the GUI might send to the engine, and holds the data about the command if applicable.
-    enum GUICommand {
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum GUICommand {
         /// Corresponds to "uci" command
         /// Is sent once on initialization. The engine doesn't really need to do anything with this.
         UCIInit,
@@ -69,7 +390,8 @@ pub mod uci {
         IsReady,
         /// Corresponds to the "setoption" command. 
         /// The engine should modify it's parameters accordingly.
-        SetEngineParameter {option_name: String, option_value: EngineParameter},
+        /// The value is kept as text, since only the engine knows what type the option is. Buttons have no value.
+        SetEngineParameter {option_name: String, option_value: Option<String>},
         /// Corresponds to the "ucinewgame" command.
         /// This indicates that the next position to be searched is not from the same game, so the engine should clear any game-local data it's kept.
         UCINewGame,
@@ -91,7 +413,8 @@ pub mod uci {
     }
 
     /// Represents the data of an ID command.
-    enum IdCommandData {
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum IdCommandData {
         /// Identifies the name of the engine
         Name(String),
         /// Identifies the author of the engine
@@ -99,7 +422,8 @@ pub mod uci {
     }
 
     /// Data for the copyprotection command
-    enum CopyprotectionCommandData 
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum CopyprotectionCommandData 
     {
         Checking,
         Ok,
@@ -107,9 +431,10 @@ pub mod uci {
     }
 
     /// Data for the "score" info
-    enum ScoreInfoData {
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum ScoreInfoData {
         /// Overall score of the position from the engine's point of view in centipawns
-        CentiPawns(usize),
+        CentiPawns(isize),
         /// Number of moves until mate. Positive means the engine wins, negative means the engine loses.
         MateInMoves(isize),
         /// Indicates that the score is a lower bound
@@ -119,7 +444,8 @@ pub mod uci {
     }
 
     /// Data for the Info command
-    enum InfoCommandData {
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum InfoCommandData {
         /// Represents "depth" info
         /// Indicates how many plies deep the search has gotten
         Depth(usize),
@@ -177,7 +503,8 @@ pub mod uci {
     }
 
     /// Represents commands the engine can pass to the GUI, including any extra data if applicable.
-    enum EngineCommand {
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum EngineCommand {
         /// Represents the "id" command.
         /// One of each type must be sent after engine initialization and before the initial uciok command and optional parameters command.
         ID(IdCommandData),
@@ -189,7 +516,7 @@ pub mod uci {
         EngineReady,
         /// Represents the "bestmove" command.
         /// Indicates that the engine has finished searching and found this move best. Optionally, the engine can send the move it would like to ponder about. It must not begin pondering unless told to do so.
-        MoveSelected {selected_move: Move, desired_ponder: Move},
+        MoveSelected {selected_move: Move, desired_ponder: Option<Move>},
         /// Represents the "copyprotection" command.
         /// The engine should send checking first, then ok or error.
         Copyprotection(CopyprotectionCommandData),
@@ -201,24 +528,908 @@ pub mod uci {
         /// All info will be sent simultaneously.
         Info(Vec<InfoCommandData>),
         /// Represents the "option" command.
+        /// Tells the GUI which parameters can be changed in the engine. Must be sent after the id commands and before uciok.
+        Option {name: String, parameter: EngineParameter}
+    }
 
+    /// Describes one of the engine's parameters and its default value, as declared to the GUI with the "option" command.
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum EngineParameter {
+        /// Either true or false.
+        Check(bool),
+        /// A whole number between min and max, inclusive.
+        Spin {default: isize, min: isize, max: isize},
+        /// One of a fixed set of strings.
+        Combo {default: String, choices: Vec<String>},
+        /// Has no value. Setting it makes the engine do something, like clearing its hash table.
+        Button,
+        /// Any string.
+        String(String)
     }
 
-    enum EngineParameter {
+    /// The value an option currently has. Mirrors EngineParameter, minus the type information.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum OptionValue {
         Check(bool),
-        Spin {min: isize, max: isize},
-        Combo(Vec<String>),
-        Button(String),
+        Spin(isize),
+        Combo(String),
+        /// A button was pressed.
+        Button,
         String(String)
     }
+
+    impl EngineParameter {
+        pub fn default_value(&self) -> OptionValue {
+            match self {
+                EngineParameter::Check(default) => OptionValue::Check(*default),
+                EngineParameter::Spin {default, ..} => OptionValue::Spin(*default),
+                EngineParameter::Combo {default, ..} => OptionValue::Combo(default.clone()),
+                EngineParameter::Button => OptionValue::Button,
+                EngineParameter::String(default) => OptionValue::String(default.clone())
+            }
+        }
+
+        /// Interprets a value sent with "setoption", checking it fits this parameter's type and range.
+        pub fn parse_value(&self, value: Option<&str>) -> Result<OptionValue, OptionError> {
+            let text = match (self, value) {
+                (EngineParameter::Button, None) => return Ok(OptionValue::Button),
+                (EngineParameter::Button, Some(value)) => return Err(OptionError::InvalidValue(value.to_string())),
+                (_, None) => return Err(OptionError::MissingValue),
+                (_, Some(value)) => value
+            };
+            let invalid = || OptionError::InvalidValue(text.to_string());
+            match self {
+                EngineParameter::Check(_) => match text {
+                    "true" => Ok(OptionValue::Check(true)),
+                    "false" => Ok(OptionValue::Check(false)),
+                    _ => Err(invalid())
+                },
+                EngineParameter::Spin {min, max, ..} => {
+                    let value: isize = text.parse().map_err(|_| invalid())?;
+                    if value < *min || value > *max {
+                        return Err(OptionError::OutOfRange {value, min: *min, max: *max});
+                    }
+                    Ok(OptionValue::Spin(value))
+                },
+                // GUIs don't always keep the case of combo choices, so match the declared spelling
+                EngineParameter::Combo {choices, ..} => choices.iter()
+                    .find(|choice| choice.eq_ignore_ascii_case(text))
+                    .map(|choice| OptionValue::Combo(choice.clone()))
+                    .ok_or_else(invalid),
+                // The spec uses "<empty>" for an empty string
+                EngineParameter::String(_) if text == "<empty>" => Ok(OptionValue::String(String::new())),
+                EngineParameter::String(_) => Ok(OptionValue::String(text.to_string())),
+                EngineParameter::Button => unreachable!("buttons are handled above")
+            }
+        }
+    }
+
+    /// Describes why a "setoption" command was rejected.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum OptionError {
+        /// The engine never declared an option with this name.
+        UnknownOption(String),
+        /// Every type except button needs a value.
+        MissingValue,
+        /// The value doesn't fit the option's type. Holds the offending value.
+        InvalidValue(String),
+        /// A spin value was outside the declared range.
+        OutOfRange {value: isize, min: isize, max: isize}
+    }
+
+    impl std::fmt::Display for OptionError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                OptionError::UnknownOption(name) => write!(f, "there is no option called \"{name}\""),
+                OptionError::MissingValue => write!(f, "no value was given"),
+                OptionError::InvalidValue(value) => write!(f, "\"{value}\" is not a valid value"),
+                OptionError::OutOfRange {value, min, max} => write!(f, "{value} is not between {min} and {max}")
+            }
+        }
+    }
+
+    impl std::error::Error for OptionError {}
+
+    /// Keeps track of the options an engine declared and their current values.
+    /// Option names are matched ignoring case, as GUIs don't always keep it.
+    #[derive(Debug, Clone, PartialEq, Default)]
+    pub struct EngineOptions {
+        options: Vec<(String, EngineParameter, OptionValue)>
+    }
+
+    impl EngineOptions {
+        /// Panics if a spin option's default isn't between its min and max, since the engine declared something the GUI can't set.
+        pub fn new(declarations: Vec<(String, EngineParameter)>) -> EngineOptions {
+            let options = declarations.into_iter().map(|(name, parameter)| {
+                if let EngineParameter::Spin {default, min, max} = parameter {
+                    assert!((min..=max).contains(&default), "the default of spin option {name} is {default}, which is not between {min} and {max}");
+                }
+                let value = parameter.default_value();
+                (name, parameter, value)
+            }).collect();
+            EngineOptions {options}
+        }
+
+        /// The "option" commands to send to the GUI, in the order the options were declared.
+        pub fn declarations(&self) -> impl Iterator<Item = EngineCommand> + '_ {
+            self.options.iter().map(|(name, parameter, _)| EngineCommand::Option {name: name.clone(), parameter: parameter.clone()})
+        }
+
+        pub fn get(&self, name: &str) -> Option<&OptionValue> {
+            self.options.iter().find(|(declared, _, _)| declared.eq_ignore_ascii_case(name)).map(|(_, _, value)| value)
+        }
+
+        /// Validates and stores a value sent with "setoption".
+        /// Returns the option's name as the engine declared it, along with the new value.
+        pub fn set(&mut self, name: &str, value: Option<&str>) -> Result<(String, OptionValue), OptionError> {
+            let (declared, parameter, current) = self.options.iter_mut()
+                .find(|(declared, _, _)| declared.eq_ignore_ascii_case(name))
+                .ok_or_else(|| OptionError::UnknownOption(name.to_string()))?;
+            let value = parameter.parse_value(value)?;
+            *current = value.clone();
+            Ok((declared.clone(), value))
+        }
+    }
  
-    trait Engine {
-        
+    /// Flags the front end uses to talk to an engine while it's searching, since the engine itself is busy on another thread.
+    /// Cloning gives another handle to the same flags.
+    #[derive(Debug, Clone, Default)]
+    pub struct SearchSignals {
+        stop: std::sync::Arc<std::sync::atomic::AtomicBool>,
+        ponder_hit: std::sync::Arc<std::sync::atomic::AtomicBool>
+    }
+
+    impl SearchSignals {
+        pub fn new() -> SearchSignals {
+            SearchSignals::default()
+        }
+
+        /// Asks the search to finish as soon as possible.
+        pub fn stop(&self) {
+            self.stop.store(true, std::sync::atomic::Ordering::Relaxed);
+        }
+
+        pub fn should_stop(&self) -> bool {
+            self.stop.load(std::sync::atomic::Ordering::Relaxed)
+        }
+
+        /// Tells a pondering search that the opponent played the expected move, so it's now searching for real.
+        pub fn ponder_hit(&self) {
+            self.ponder_hit.store(true, std::sync::atomic::Ordering::Relaxed);
+        }
+
+        pub fn is_ponder_hit(&self) -> bool {
+            self.ponder_hit.load(std::sync::atomic::Ordering::Relaxed)
+        }
+
+        /// Clears both flags, ready for the next search.
+        pub fn reset(&self) {
+            self.stop.store(false, std::sync::atomic::Ordering::Relaxed);
+            self.ponder_hit.store(false, std::sync::atomic::Ordering::Relaxed);
+        }
+
+        /// Blocks until a search that's finished thinking is allowed to answer.
+        /// Infinite searches have to wait for stop, and ponder searches for either stop or a ponder hit. Anything else returns straight away.
+        pub fn wait_until_released(&self, limits: &SearchLimits) {
+            while (limits.infinite || (limits.ponder && !self.is_ponder_hit())) && !self.should_stop() {
+                std::thread::sleep(std::time::Duration::from_millis(1));
+            }
+        }
     }
 
-    struct UCIInterface {
+    /// What a search settled on. Becomes the "bestmove" command.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct BestMove {
+        pub selected_move: Move,
+        /// The reply the engine expects, if it would like to ponder on it.
+        pub ponder: Option<Move>
+    }
 
+    impl From<BestMove> for EngineCommand {
+        fn from(best: BestMove) -> Self {
+            EngineCommand::MoveSelected {selected_move: best.selected_move, desired_ponder: best.ponder}
+        }
     }
 
-    
-}
\ No newline at end of file
+    /// Everything an engine has to provide to be driven by the UCI front end.
+    /// The front end takes care of the protocol, so engines only ever see already parsed commands.
+    /// Searches run on their own thread, which is why "stop" and "ponderhit" arrive through SearchSignals instead of method calls.
+    pub trait Engine: Send {
+        /// Sent as "id name".
+        fn name(&self) -> String;
+
+        /// Sent as "id author".
+        fn author(&self) -> String;
+
+        /// The parameters the GUI is allowed to change, sent as "option" commands at startup.
+        /// Only asked for once, when the front end starts.
+        fn options(&self) -> Vec<(String, EngineParameter)> {
+            Vec::new()
+        }
+
+        /// Called when the GUI changes one of the declared options, after the value has been checked against its type and range.
+        /// The name is spelled as it was declared.
+        fn set_option(&mut self, _name: &str, _value: &OptionValue) {}
+
+        /// Called when the GUI presses one of the declared button options.
+        fn button_pressed(&mut self, _name: &str) {}
+
+        /// Called for "debug". The engine may send extra info strings while it's on.
+        fn set_debug(&mut self, _debug: bool) {}
+
+        /// Called for "ucinewgame". The engine should forget anything it learned about the previous game.
+        fn new_game(&mut self) {}
+
+        /// Called for "position", with the moves already played and checked.
+        fn set_position(&mut self, game: crate::game::Game);
+
+        /// Called for "go". Searches the last position set, within the given limits, and returns the best move found.
+        /// The search must return soon after `signals.should_stop()` becomes true, and must always return a move if there is one, or the null move if the game is already over.
+        /// If the search is infinite, it must not return until stop is signalled.
+        /// If it's a ponder search, it must not return until either stop is signalled, or `signals.is_ponder_hit()` becomes true and the limits run out.
+        /// `signals.wait_until_released` waits out both cases.
+        /// Progress can be reported at any time by calling `info`.
+        fn go(&mut self, limits: &SearchLimits, signals: &SearchSignals, info: &mut dyn FnMut(Vec<InfoCommandData>)) -> BestMove;
+    }
+
+    /// Everything the main loop of UCIInterface waits on, from both the reader and the search thread.
+    enum Event<E> {
+        Command(GUICommand),
+        InvalidCommand(ParseError),
+        InputClosed,
+        Info(Vec<InfoCommandData>),
+        SearchFinished(E, BestMove)
+    }
+
+    /// Drives an engine over UCI, reading commands from `input` and writing responses to `output`.
+    /// Input is read on its own thread and searches run on another, so "stop", "isready" and "quit" are answered straight away even while the engine is thinking.
+    /// Commands that need the engine while it's searching are held back until the search finishes.
+    pub struct UCIInterface<E, R, W> {
+        /// None while the engine is away on the search thread.
+        engine: Option<E>,
+        /// Handed over to the reader thread when the interface starts running.
+        input: Option<R>,
+        output: W,
+        signals: SearchSignals,
+        /// Commands that arrived while the engine was searching.
+        pending: std::collections::VecDeque<GUICommand>,
+        options: EngineOptions,
+        debug: bool,
+        /// Once the input ends, nothing could ever stop a search, so searches are stopped straight away.
+        input_closed: bool,
+        /// The opening book set with the BookFile option, if any.
+        book: Option<crate::polyglot::Book>,
+        /// The last position set, kept so the book can be looked up without asking the engine.
+        board: crate::board::Board,
+        /// For picking between book moves.
+        rng: crate::rng::Rng
+    }
+
+    /// Added to every engine's options and handled by the front end itself. When on, "go" is answered from the book while it has moves.
+    const OWN_BOOK: &str = "OwnBook";
+    /// The path of the Polyglot book to use with OwnBook.
+    const BOOK_FILE: &str = "BookFile";
+
+    impl<E: Engine + 'static> UCIInterface<E, std::io::BufReader<std::io::Stdin>, std::io::Stdout> {
+        /// Talks to the GUI over standard input and output, which is how UCI engines are normally run.
+        pub fn with_stdio(engine: E) -> Self {
+            UCIInterface::new(engine, std::io::BufReader::new(std::io::stdin()), std::io::stdout())
+        }
+    }
+
+    impl<E: Engine + 'static, R: BufRead + Send + 'static, W: Write> UCIInterface<E, R, W> {
+        pub fn new(engine: E, input: R, output: W) -> Self {
+            let mut declarations = engine.options();
+            declarations.push((OWN_BOOK.to_string(), EngineParameter::Check(false)));
+            declarations.push((BOOK_FILE.to_string(), EngineParameter::String(String::new())));
+            UCIInterface {
+                options: EngineOptions::new(declarations),
+                engine: Some(engine),
+                input: Some(input),
+                output,
+                signals: SearchSignals::new(),
+                pending: std::collections::VecDeque::new(),
+                debug: false,
+                input_closed: false,
+                book: None,
+                board: crate::board::Board::start_position(),
+                rng: crate::rng::Rng::from_entropy()
+            }
+        }
+
+        /// Runs until "quit" is received or the input ends.
+        /// If the input ends during a search, the search is stopped and its best move is still sent.
+        pub fn run(mut self) -> std::io::Result<()> {
+            let (sender, receiver) = mpsc::channel();
+            let input = self.input.take().expect("the interface only runs once");
+            let reader_sender = sender.clone();
+            // The reader is never joined: it may be blocked on input forever after "quit"
+            thread::spawn(move || {
+                for line in input.lines() {
+                    let Ok(line) = line else { break };
+                    let event = match line.parse() {
+                        Ok(command) => Event::Command(command),
+                        Err(error) => Event::InvalidCommand(error)
+                    };
+                    if reader_sender.send(event).is_err() {
+                        return;
+                    }
+                }
+                let _ = reader_sender.send(Event::InputClosed);
+            });
+
+            while let Ok(event) = receiver.recv() {
+                match event {
+                    Event::Command(GUICommand::Quit) => {
+                        self.signals.stop();
+                        return Ok(());
+                    },
+                    // A stop or ponderhit sent after a go that's still waiting its turn is meant for that search, not the current one
+                    Event::Command(command @ (GUICommand::Stop | GUICommand::PonderHit)) if self.pending.iter().any(|queued| matches!(queued, GUICommand::Go(_))) => {
+                        self.pending.push_back(command);
+                    },
+                    Event::Command(GUICommand::IsReady) => {
+                        send(&mut self.output, EngineCommand::EngineReady)?;
+                    },
+                    Event::Command(GUICommand::Stop) => {
+                        self.signals.stop();
+                    },
+                    Event::Command(GUICommand::PonderHit) => {
+                        self.signals.ponder_hit();
+                    },
+                    Event::Command(command) => {
+                        self.pending.push_back(command);
+                    },
+                    Event::InvalidCommand(error) => {
+                        // Unknown commands are supposed to be ignored, but they're useful to know about while debugging
+                        if self.debug && error != ParseError::NoCommand {
+                            send_string(&mut self.output, format!("ignoring command: {error}"))?;
+                        }
+                    },
+                    Event::InputClosed => {
+                        self.input_closed = true;
+                        self.signals.stop();
+                    },
+                    Event::Info(data) => {
+                        send_info(&mut self.output, data)?;
+                    },
+                    Event::SearchFinished(engine, best) => {
+                        self.engine = Some(engine);
+                        send(&mut self.output, best.into())?;
+                    }
+                }
+                self.process_pending(&sender)?;
+                if self.input_closed && self.engine.is_some() {
+                    break;
+                }
+            }
+            Ok(())
+        }
+
+        /// Handles queued commands in order, stopping at the first one that needs the engine while it's searching.
+        /// "isready", "stop" and "ponderhit" are handled as soon as they arrive, so they're normally never queued.
+        fn process_pending(&mut self, sender: &mpsc::Sender<Event<E>>) -> std::io::Result<()> {
+            while let Some(command) = self.pending.pop_front() {
+                // These only end up queued behind a go, and apply to its search once it has started
+                match command {
+                    GUICommand::Stop => {
+                        self.signals.stop();
+                        continue;
+                    },
+                    GUICommand::PonderHit => {
+                        self.signals.ponder_hit();
+                        continue;
+                    },
+                    _ => ()
+                }
+
+                let Some(engine) = self.engine.as_mut() else {
+                    self.pending.push_front(command);
+                    return Ok(());
+                };
+                match command {
+                    GUICommand::UCIInit => {
+                        send(&mut self.output, EngineCommand::ID(IdCommandData::Name(engine.name())))?;
+                        send(&mut self.output, EngineCommand::ID(IdCommandData::Author(engine.author())))?;
+                        for declaration in self.options.declarations() {
+                            send(&mut self.output, declaration)?;
+                        }
+                        send(&mut self.output, EngineCommand::EngineInitialized)?;
+                    },
+                    GUICommand::DebugMode(debug) => {
+                        self.debug = debug;
+                        engine.set_debug(debug);
+                    },
+                    GUICommand::SetEngineParameter {option_name, option_value} => match self.options.set(&option_name, option_value.as_deref()) {
+                        Ok((name, _)) if name == OWN_BOOK => (),
+                        Ok((name, OptionValue::String(path))) if name == BOOK_FILE => {
+                            self.book = None;
+                            if !path.is_empty() {
+                                match crate::polyglot::Book::open(&path) {
+                                    Ok(book) => self.book = Some(book),
+                                    Err(error) => send_string(&mut self.output, format!("could not load book {path}: {error}"))?
+                                }
+                            }
+                        },
+                        Ok((name, OptionValue::Button)) => engine.button_pressed(&name),
+                        Ok((name, value)) => engine.set_option(&name, &value),
+                        Err(error) => send_string(&mut self.output, format!("ignoring setoption: {error}"))?
+                    },
+                    GUICommand::UCINewGame => engine.new_game(),
+                    GUICommand::Position(position) => match position.replay() {
+                        Ok(game) => {
+                            self.board = game.board().clone();
+                            engine.set_position(game);
+                        },
+                        Err(error) => send_string(&mut self.output, format!("ignoring position: {error}"))?
+                    },
+                    GUICommand::Go(subcommands) => {
+                        // There's no sensible way to search with contradictory limits, so say why and wait for the next command
+                        let limits = match SearchLimits::from_go(&subcommands) {
+                            Ok(limits) => limits,
+                            Err(error) => {
+                                send_string(&mut self.output, format!("ignoring go: {error}"))?;
+                                continue;
+                            }
+                        };
+                        if let Some(best) = self.book_move(&limits) {
+                            send(&mut self.output, best.into())?;
+                            continue;
+                        }
+                        let mut engine = self.engine.take().expect("the engine is idle");
+                        self.signals.reset();
+                        if self.input_closed {
+                            self.signals.stop();
+                        }
+                        let signals = self.signals.clone();
+                        let sender = sender.clone();
+                        thread::spawn(move || {
+                            let info_sender = sender.clone();
+                            let best = engine.go(&limits, &signals, &mut |data| {
+                                let _ = info_sender.send(Event::Info(data));
+                            });
+                            let _ = sender.send(Event::SearchFinished(engine, best));
+                        });
+                    },
+                    GUICommand::IsReady | GUICommand::Stop | GUICommand::PonderHit | GUICommand::Quit => ()
+                }
+            }
+            Ok(())
+        }
+
+        /// Picks a move from the opening book, if OwnBook is on and the book has one for the current position.
+        /// Infinite and ponder searches aren't answered from the book, since they mustn't finish until the GUI says so.
+        fn book_move(&mut self, limits: &SearchLimits) -> Option<BestMove> {
+            if limits.infinite || limits.ponder || self.options.get(OWN_BOOK) != Some(&OptionValue::Check(true)) {
+                return None;
+            }
+            let moves: Vec<(Move, u16)> = self.book.as_ref()?.moves(&self.board).into_iter()
+                .filter(|(m, _)| limits.search_moves.is_empty() || limits.search_moves.contains(m))
+                .collect();
+            let selected_move = crate::polyglot::pick_move(&moves, crate::polyglot::BookChoice::Weighted, &mut self.rng)?;
+            Some(BestMove {selected_move, ponder: None})
+        }
+    }
+
+    /// Writes a command to the GUI, flushing straight away so it isn't left sitting in a buffer.
+    fn send(output: &mut impl Write, command: EngineCommand) -> std::io::Result<()> {
+        writeln!(output, "{command}")?;
+        output.flush()
+    }
+
+    /// Sends info from the engine. An info command can only hold one string, so any more are sent in info commands of their own.
+    fn send_info(output: &mut impl Write, data: Vec<InfoCommandData>) -> std::io::Result<()> {
+        let (strings, mut rest): (Vec<_>, Vec<_>) = data.into_iter().partition(|info| matches!(info, InfoCommandData::InfoString(_)));
+        let mut strings = strings.into_iter();
+        rest.extend(strings.next());
+        if !rest.is_empty() {
+            send(output, EngineCommand::info(rest).expect("holds at most one string"))?;
+        }
+        for string in strings {
+            send(output, EngineCommand::info(vec![string]).expect("holds one string"))?;
+        }
+        Ok(())
+    }
+
+    /// Tells the GUI something in an info string, which it can show to the user.
+    fn send_string(output: &mut impl Write, string: String) -> std::io::Result<()> {
+        send_info(output, vec![InfoCommandData::InfoString(string)])
+    }
+
+    impl std::fmt::Display for Move {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Move::Null => write!(f, "0000"),
+                Move::Normal {from, to, promotion} => {
+                    write!(f, "{from}{to}")?;
+                    if let Some(piece) = promotion {
+                        write!(f, "{}", piece.to_char())?;
+                    }
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    /// Writes a list of moves separated by spaces, as used by "pv", "refutation" and "currline".
+    fn write_moves(f: &mut std::fmt::Formatter<'_>, moves: &[Move]) -> std::fmt::Result {
+        for (index, m) in moves.iter().enumerate() {
+            if index > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{m}")?;
+        }
+        Ok(())
+    }
+
+    impl std::fmt::Display for IdCommandData {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                IdCommandData::Name(name) => write!(f, "name {name}"),
+                IdCommandData::Author(author) => write!(f, "author {author}")
+            }
+        }
+    }
+
+    impl std::fmt::Display for CopyprotectionCommandData {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                CopyprotectionCommandData::Checking => write!(f, "checking"),
+                CopyprotectionCommandData::Ok => write!(f, "ok"),
+                CopyprotectionCommandData::Error => write!(f, "error")
+            }
+        }
+    }
+
+    /// Writes the data without the leading "score", so that several of them can share one.
+    impl std::fmt::Display for ScoreInfoData {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                ScoreInfoData::CentiPawns(score) => write!(f, "cp {score}"),
+                ScoreInfoData::MateInMoves(moves) => write!(f, "mate {moves}"),
+                ScoreInfoData::ScoreIsLowerBound => write!(f, "lowerbound"),
+                ScoreInfoData::ScoreIsUpperBound => write!(f, "upperbound")
+            }
+        }
+    }
+
+    impl std::fmt::Display for InfoCommandData {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                InfoCommandData::Depth(depth) => write!(f, "depth {depth}"),
+                InfoCommandData::SelectiveDepth(depth) => write!(f, "seldepth {depth}"),
+                InfoCommandData::TimeSpentSearching(time) => write!(f, "time {time}"),
+                InfoCommandData::NodesSearched(nodes) => write!(f, "nodes {nodes}"),
+                InfoCommandData::PrincipleVariation(moves) => {
+                    write!(f, "pv")?;
+                    if !moves.is_empty() {
+                        write!(f, " ")?;
+                    }
+                    write_moves(f, moves)
+                },
+                InfoCommandData::Score(score) => write!(f, "score {score}"),
+                InfoCommandData::CurrentMove(m) => write!(f, "currmove {m}"),
+                InfoCommandData::CurrentMoveNumber(number) => write!(f, "currmovenumber {number}"),
+                InfoCommandData::HashFullPermill(permill) => write!(f, "hashfull {permill}"),
+                InfoCommandData::NodesPerSecond(nps) => write!(f, "nps {nps}"),
+                InfoCommandData::TableBaseHits(hits) => write!(f, "tbhits {hits}"),
+                InfoCommandData::ShredderDatabaseHits(hits) => write!(f, "sbhits {hits}"),
+                InfoCommandData::CpuLoad(load) => write!(f, "cpuload {load}"),
+                InfoCommandData::InfoString(string) => write!(f, "string {string}"),
+                InfoCommandData::Refutation {refuted_move, refutation} => {
+                    write!(f, "refutation {refuted_move}")?;
+                    if !refutation.is_empty() {
+                        write!(f, " ")?;
+                    }
+                    write_moves(f, refutation)
+                },
+                InfoCommandData::CurrentMoveSequence {cpu_number, sequence} => {
+                    write!(f, "currline")?;
+                    if let Some(cpu_number) = cpu_number {
+                        write!(f, " {cpu_number}")?;
+                    }
+                    if !sequence.is_empty() {
+                        write!(f, " ")?;
+                    }
+                    write_moves(f, sequence)
+                }
+            }
+        }
+    }
+
+    /// Writes the part of an "option" command that comes after the name.
+    impl std::fmt::Display for EngineParameter {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                EngineParameter::Check(default) => write!(f, "type check default {default}"),
+                EngineParameter::Spin {default, min, max} => write!(f, "type spin default {default} min {min} max {max}"),
+                EngineParameter::Combo {default, choices} => {
+                    write!(f, "type combo default {default}")?;
+                    for choice in choices {
+                        write!(f, " var {choice}")?;
+                    }
+                    Ok(())
+                },
+                EngineParameter::Button => write!(f, "type button"),
+                EngineParameter::String(default) if default.is_empty() => write!(f, "type string default <empty>"),
+                EngineParameter::String(default) => write!(f, "type string default {default}")
+            }
+        }
+    }
+
+    /// Returned when trying to build an info command with more than one string info in it.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct TooManyInfoStrings;
+
+    impl std::fmt::Display for TooManyInfoStrings {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "an info command can contain at most one string")
+        }
+    }
+
+    impl std::error::Error for TooManyInfoStrings {}
+
+    impl EngineCommand {
+        /// Builds an info command, checking that there is at most one string info in it.
+        /// Prefer this over constructing EngineCommand::Info directly.
+        pub fn info(data: Vec<InfoCommandData>) -> Result<EngineCommand, TooManyInfoStrings> {
+            let strings = data.iter().filter(|info| matches!(info, InfoCommandData::InfoString(_))).count();
+            if strings > 1 {
+                return Err(TooManyInfoStrings);
+            }
+            Ok(EngineCommand::Info(data))
+        }
+    }
+
+    /// Writes the command exactly as it should be sent to the GUI, without the trailing newline.
+    impl std::fmt::Display for EngineCommand {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                EngineCommand::ID(data) => write!(f, "id {data}"),
+                EngineCommand::EngineInitialized => write!(f, "uciok"),
+                EngineCommand::EngineReady => write!(f, "readyok"),
+                EngineCommand::MoveSelected {selected_move, desired_ponder} => {
+                    write!(f, "bestmove {selected_move}")?;
+                    if let Some(ponder) = desired_ponder {
+                        write!(f, " ponder {ponder}")?;
+                    }
+                    Ok(())
+                },
+                EngineCommand::Copyprotection(data) => write!(f, "copyprotection {data}"),
+                EngineCommand::Registration(data) => write!(f, "registration {data}"),
+                EngineCommand::Info(data) => {
+                    write!(f, "info")?;
+                    let mut previous_was_score = false;
+                    for info in data {
+                        match info {
+                            // The GUI reads everything after "string" as part of the string, so strings go last
+                            InfoCommandData::InfoString(_) => continue,
+                            // Bounds have to follow the score they belong to, as in "score cp 20 lowerbound"
+                            InfoCommandData::Score(score) if previous_was_score => write!(f, " {score}")?,
+                            info => write!(f, " {info}")?
+                        }
+                        previous_was_score = matches!(info, InfoCommandData::Score(_));
+                    }
+                    let mut strings = data.iter().filter_map(|info| match info {
+                        InfoCommandData::InfoString(string) => Some(string),
+                        _ => None
+                    });
+                    if let Some(first) = strings.next() {
+                        write!(f, " string {first}")?;
+                    }
+                    // Only happens if the command wasn't built with EngineCommand::info. Each string still needs an info of its own.
+                    for string in strings {
+                        write!(f, "\ninfo string {string}")?;
+                    }
+                    Ok(())
+                },
+                EngineCommand::Option {name, parameter} => write!(f, "option name {name} {parameter}")
+            }
+        }
+    }
+
+    /// Describes why a Position couldn't be set up on a board.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum PositionError {
+        InvalidFen(crate::board::FenError),
+        /// One of the moves wasn't legal in the position it was played in. The index counts from 0.
+        IllegalMove {index: usize, illegal_move: Move}
+    }
+
+    impl std::fmt::Display for PositionError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                PositionError::InvalidFen(error) => write!(f, "invalid FEN: {error}"),
+                PositionError::IllegalMove {index, illegal_move} => write!(f, "move {} ({illegal_move}) is illegal", index + 1)
+            }
+        }
+    }
+
+    impl std::error::Error for PositionError {}
+
+    impl From<crate::board::FenError> for PositionError {
+        fn from(error: crate::board::FenError) -> Self {
+            PositionError::InvalidFen(error)
+        }
+    }
+
+    impl Position {
+        /// Sets up the base position and plays every move on it, checking that each one is legal.
+        /// The positions along the way are kept, so repetitions can be detected.
+        pub fn replay(&self) -> Result<crate::game::Game, PositionError> {
+            let start = match &self.base {
+                PositionBase::Fen(fen) => crate::board::Board::from_fen(fen)?,
+                PositionBase::StartPosition => crate::board::Board::start_position()
+            };
+            let mut game = crate::game::Game::new(start);
+            for (index, &m) in self.moves.iter().enumerate() {
+                game.play(m).map_err(|_| PositionError::IllegalMove {index, illegal_move: m})?;
+            }
+            Ok(game)
+        }
+    }
+
+    /// Describes why a line from the GUI couldn't be turned into a GUICommand.
+    /// Wherever it makes sense, the offending token is included so it can be logged.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum ParseError {
+        /// The line didn't contain any known command, so the whole thing should be ignored.
+        NoCommand,
+        /// The command ended before an argument it needs. Holds the name of the command or subcommand.
+        MissingArgument(&'static str),
+        /// A token showed up somewhere it doesn't make sense.
+        UnexpectedToken(String),
+        /// A token that should have been a number wasn't one.
+        InvalidNumber(String),
+        /// A token that should have been a move wasn't one.
+        InvalidMove(String)
+    }
+
+    impl std::fmt::Display for ParseError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                ParseError::NoCommand => write!(f, "no known command found"),
+                ParseError::MissingArgument(command) => write!(f, "missing argument for \"{command}\""),
+                ParseError::UnexpectedToken(token) => write!(f, "unexpected token \"{token}\""),
+                ParseError::InvalidNumber(token) => write!(f, "\"{token}\" is not a valid number"),
+                ParseError::InvalidMove(token) => write!(f, "\"{token}\" is not a valid move")
+            }
+        }
+    }
+
+    impl std::error::Error for ParseError {}
+
+    impl std::str::FromStr for GUICommand {
+        type Err = ParseError;
+
+        /// Parses a single line sent by the GUI.
+        /// As the UCI spec requires, unknown tokens before the command are skipped, so "joho debug on" is the same as "debug on".
+        fn from_str(line: &str) -> Result<Self, Self::Err> {
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+            for (index, token) in tokens.iter().enumerate() {
+                let args = &tokens[index + 1..];
+                let command = match *token {
+                    "uci" => GUICommand::UCIInit,
+                    "debug" => parse_debug(args)?,
+                    "isready" => GUICommand::IsReady,
+                    "setoption" => parse_setoption(args)?,
+                    "ucinewgame" => GUICommand::UCINewGame,
+                    "position" => GUICommand::Position(parse_position(args)?),
+                    "go" => GUICommand::Go(parse_go(args)?),
+                    "stop" => GUICommand::Stop,
+                    "ponderhit" => GUICommand::PonderHit,
+                    "quit" => GUICommand::Quit,
+                    _ => continue
+                };
+                return Ok(command);
+            }
+            Err(ParseError::NoCommand)
+        }
+    }
+
+    fn parse_debug(args: &[&str]) -> Result<GUICommand, ParseError> {
+        match args.first() {
+            Some(&"on") => Ok(GUICommand::DebugMode(true)),
+            Some(&"off") => Ok(GUICommand::DebugMode(false)),
+            Some(token) => Err(ParseError::UnexpectedToken(token.to_string())),
+            None => Err(ParseError::MissingArgument("debug"))
+        }
+    }
+
+    /// Option names and values can both contain spaces, so everything between "name" and "value" is the name, and everything after "value" is the value.
+    fn parse_setoption(args: &[&str]) -> Result<GUICommand, ParseError> {
+        match args.first() {
+            Some(&"name") => (),
+            Some(token) => return Err(ParseError::UnexpectedToken(token.to_string())),
+            None => return Err(ParseError::MissingArgument("setoption"))
+        }
+        let args = &args[1..];
+        let value_index = args.iter().position(|token| *token == "value");
+        let name_tokens = &args[..value_index.unwrap_or(args.len())];
+        if name_tokens.is_empty() {
+            return Err(ParseError::MissingArgument("name"));
+        }
+        let option_name = name_tokens.join(" ");
+        let option_value = value_index.map(|index| args[index + 1..].join(" "));
+        Ok(GUICommand::SetEngineParameter {option_name, option_value})
+    }
+
+    fn parse_position(args: &[&str]) -> Result<Position, ParseError> {
+        let moves_index = args.iter().position(|token| *token == "moves");
+        let (base, moves) = match moves_index {
+            Some(index) => (&args[..index], Some(&args[index + 1..])),
+            None => (args, None)
+        };
+        let base = match base.split_first() {
+            Some((&"startpos", [])) => PositionBase::StartPosition,
+            Some((&"startpos", [token, ..])) => return Err(ParseError::UnexpectedToken(token.to_string())),
+            Some((&"fen", [])) => return Err(ParseError::MissingArgument("fen")),
+            Some((&"fen", fields)) => PositionBase::Fen(fields.join(" ")),
+            Some((token, _)) => return Err(ParseError::UnexpectedToken(token.to_string())),
+            None => return Err(ParseError::MissingArgument("position"))
+        };
+        let moves = match moves {
+            Some(moves) => parse_moves(moves)?,
+            None => Vec::new()
+        };
+        Ok(Position {base, moves})
+    }
+
+    fn parse_moves(tokens: &[&str]) -> Result<Vec<Move>, ParseError> {
+        tokens.iter().map(|token| parse_move(token)).collect()
+    }
+
+    fn parse_move(token: &str) -> Result<Move, ParseError> {
+        token.parse().map_err(|_| ParseError::InvalidMove(token.to_string()))
+    }
+
+    /// Every keyword that can start a subcommand of "go". Used to know where the list of moves after "searchmoves" ends.
+    const GO_KEYWORDS: [&str; 12] = ["searchmoves", "ponder", "wtime", "btime", "winc", "binc", "movestogo", "depth", "nodes", "mate", "movetime", "infinite"];
+
+    fn parse_go(args: &[&str]) -> Result<Vec<GoCommand>, ParseError> {
+        let mut subcommands = Vec::new();
+        let mut tokens = args.iter().peekable();
+        while let Some(&token) = tokens.next() {
+            let subcommand = match token {
+                "searchmoves" => {
+                    let mut moves = Vec::new();
+                    while let Some(&&token) = tokens.peek() {
+                        if GO_KEYWORDS.contains(&token) {
+                            break;
+                        }
+                        moves.push(parse_move(token)?);
+                        tokens.next();
+                    }
+                    GoCommand::SearchMoves(moves)
+                },
+                "ponder" => GoCommand::Ponder,
+                "infinite" => GoCommand::InfiniteSearch,
+                "wtime" => GoCommand::WhiteClockLeft(parse_clock("wtime", tokens.next())?),
+                "btime" => GoCommand::BlackClockLeft(parse_clock("btime", tokens.next())?),
+                "winc" => GoCommand::WhiteIncrement(parse_clock("winc", tokens.next())?),
+                "binc" => GoCommand::BlackIncrement(parse_clock("binc", tokens.next())?),
+                "movestogo" => GoCommand::MovesToGo(parse_number("movestogo", tokens.next())?),
+                "depth" => GoCommand::MaxSearchDepth(parse_number("depth", tokens.next())?),
+                "nodes" => GoCommand::MaxSearchNodes(parse_number("nodes", tokens.next())?),
+                "mate" => GoCommand::Mate(parse_number("mate", tokens.next())?),
+                "movetime" => GoCommand::TargetSearchTime(parse_number("movetime", tokens.next())?),
+                // Unknown tokens are ignored, as the UCI spec requires
+                _ => continue
+            };
+            subcommands.push(subcommand);
+        }
+        Ok(subcommands)
+    }
+
+    fn parse_number(subcommand: &'static str, token: Option<&&str>) -> Result<usize, ParseError> {
+        let token = token.ok_or(ParseError::MissingArgument(subcommand))?;
+        token.parse().map_err(|_| ParseError::InvalidNumber(token.to_string()))
+    }
+
+    /// Some GUIs send a negative time when a side has overstepped its clock, which is treated as no time left.
+    fn parse_clock(subcommand: &'static str, token: Option<&&str>) -> Result<usize, ParseError> {
+        let token = token.ok_or(ParseError::MissingArgument(subcommand))?;
+        let milliseconds: i64 = token.parse().map_err(|_| ParseError::InvalidNumber(token.to_string()))?;
+        Ok(milliseconds.max(0) as usize)
+    }
+}
@@ -0,0 +1,120 @@
+use crate::attacks::{bishop_attacks, king_attacks, knight_attacks, queen_attacks, rook_attacks};
+use crate::bitboard::Bitboard;
+use crate::board::{Board, CastlingRights, Color, PieceKind};
+use crate::uci::{Move, PromotionPiece, Square};
+
+const PROMOTIONS: [PromotionPiece; 4] = [PromotionPiece::Queen, PromotionPiece::Rook, PromotionPiece::Bishop, PromotionPiece::Knight];
+
+/// Returns every legal move in the position.
+/// Moves are generated as if pins and checks didn't exist, then each one is tried on a copy of the board and kept only if it doesn't leave the king attacked.
+/// That one test covers pins, double checks, king moves into check and the en passant capture that uncovers an attack along the rank.
+pub fn legal_moves(board: &Board) -> Vec<Move> {
+    let mut moves = Vec::with_capacity(64);
+    pseudo_legal_moves(board, &mut moves);
+    let mover = board.side_to_move();
+    let mut scratch = board.clone();
+    moves.retain(|&m| {
+        let undo = scratch.make_move(m);
+        let legal = !scratch.is_square_attacked(scratch.king_square(mover), mover.opponent());
+        scratch.unmake_move(m, undo);
+        debug_assert_eq!(&scratch, board, "unmaking {m} didn't restore the board");
+        legal
+    });
+    moves
+}
+
+/// Returns true if the move is one of the legal moves in the position.
+pub fn is_legal(board: &Board, m: Move) -> bool {
+    legal_moves(board).contains(&m)
+}
+
+/// Generates every move that follows the movement rules of the pieces, without checking if the king is left in check.
+/// Castling is the exception: it's only generated if the king doesn't start, pass through or end up in check.
+fn pseudo_legal_moves(board: &Board, moves: &mut Vec<Move>) {
+    let mover = board.side_to_move();
+    let occupied = board.occupied();
+    let targets = !board.color(mover);
+
+    pawn_moves(board, moves);
+    for from in board.pieces_of(mover, PieceKind::Knight) {
+        push_moves(from, knight_attacks(from) & targets, moves);
+    }
+    for from in board.pieces_of(mover, PieceKind::Bishop) {
+        push_moves(from, bishop_attacks(from, occupied) & targets, moves);
+    }
+    for from in board.pieces_of(mover, PieceKind::Rook) {
+        push_moves(from, rook_attacks(from, occupied) & targets, moves);
+    }
+    for from in board.pieces_of(mover, PieceKind::Queen) {
+        push_moves(from, queen_attacks(from, occupied) & targets, moves);
+    }
+    let king = board.king_square(mover);
+    push_moves(king, king_attacks(king) & targets, moves);
+    castling_moves(board, king, moves);
+}
+
+fn push_moves(from: Square, targets: Bitboard, moves: &mut Vec<Move>) {
+    moves.extend(targets.into_iter().map(|to| Move::new(from, to)));
+}
+
+/// Pushes a pawn move for every target, or all four promotions for targets on the last rank.
+/// `offset` is how far the targets are from the squares the pawns moved from.
+fn push_pawn_moves(targets: Bitboard, offset: i8, moves: &mut Vec<Move>) {
+    for to in targets {
+        let from = Square::from_index((to.index() as i8 - offset) as u8).expect("pawns move from squares on the board");
+        if to.rank() == 0 || to.rank() == 7 {
+            moves.extend(PROMOTIONS.iter().map(|&piece| Move::with_promotion(from, to, piece)));
+        } else {
+            moves.push(Move::new(from, to));
+        }
+    }
+}
+
+/// Generates the moves of every pawn at once, by shifting the whole set of pawns.
+fn pawn_moves(board: &Board, moves: &mut Vec<Move>) {
+    let mover = board.side_to_move();
+    let pawns = board.pieces_of(mover, PieceKind::Pawn);
+    let empty = !board.occupied();
+    let enemies = board.color(mover.opponent());
+    let (forward, double_rank) = match mover {
+        Color::White => (8, Bitboard::rank(3)),
+        Color::Black => (-8, Bitboard::rank(4))
+    };
+
+    let single = pawns.forward(mover) & empty;
+    push_pawn_moves(single, forward, moves);
+    push_pawn_moves(single.forward(mover) & empty & double_rank, 2 * forward, moves);
+
+    let en_passant = board.en_passant().map_or(Bitboard::EMPTY, Bitboard::from_square);
+    let capturable = enemies | en_passant;
+    // Shifting east then forward moves a pawn one file right, so it started one square less far along than a straight push
+    push_pawn_moves(pawns.forward(mover).east() & capturable, forward + 1, moves);
+    push_pawn_moves(pawns.forward(mover).west() & capturable, forward - 1, moves);
+}
+
+fn castling_moves(board: &Board, king: Square, moves: &mut Vec<Move>) {
+    let mover = board.side_to_move();
+    let opponent = mover.opponent();
+    let rank = mover.back_rank();
+    if king != Square::new(4, rank) || board.is_square_attacked(king, opponent) {
+        return;
+    }
+
+    // (right, files that must be empty, files the king crosses or lands on, where the king ends up)
+    let sides = [
+        (CastlingRights::kingside(mover), &[5, 6][..], [5, 6], 6),
+        (CastlingRights::queenside(mover), &[1, 2, 3][..], [3, 2], 2)
+    ];
+    for (right, empty, crossed, target) in sides {
+        if !board.castling_rights().contains(right) {
+            continue;
+        }
+        if empty.iter().any(|&file| board.occupied().contains(Square::new(file, rank))) {
+            continue;
+        }
+        if crossed.iter().any(|&file| board.is_square_attacked(Square::new(file, rank), opponent)) {
+            continue;
+        }
+        moves.push(Move::new(king, Square::new(target, rank)));
+    }
+}
@@ -0,0 +1,104 @@
+use crate::board::{Board, PieceKind};
+use crate::movegen::legal_moves;
+use crate::uci::Move;
+
+/// A breakdown of the leaf nodes counted by perft_stats, classified by the move that reached them.
+/// This is the same breakdown the Chess Programming Wiki gives for its reference positions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PerftStats {
+    pub nodes: u64,
+    /// Includes en passant captures.
+    pub captures: u64,
+    pub en_passants: u64,
+    pub castles: u64,
+    pub promotions: u64,
+    /// Includes checkmates.
+    pub checks: u64,
+    pub checkmates: u64
+}
+
+impl std::ops::AddAssign for PerftStats {
+    fn add_assign(&mut self, other: PerftStats) {
+        self.nodes += other.nodes;
+        self.captures += other.captures;
+        self.en_passants += other.en_passants;
+        self.castles += other.castles;
+        self.promotions += other.promotions;
+        self.checks += other.checks;
+        self.checkmates += other.checkmates;
+    }
+}
+
+/// Counts the positions reachable in exactly `depth` plies.
+pub fn perft(board: &Board, depth: u32) -> u64 {
+    count_leaves(&mut board.clone(), depth)
+}
+
+/// Does the work of perft, making and unmaking moves on one board instead of copying it for every move.
+fn count_leaves(board: &mut Board, depth: u32) -> u64 {
+    if depth == 0 {
+        return 1;
+    }
+    let moves = legal_moves(board);
+    // Every legal move leads to exactly one leaf, so there's no need to play them
+    if depth == 1 {
+        return moves.len() as u64;
+    }
+    moves.into_iter().map(|m| {
+        let undo = board.make_move(m);
+        let leaves = count_leaves(board, depth - 1);
+        board.unmake_move(m, undo);
+        leaves
+    }).sum()
+}
+
+/// Like perft, but also classifies every leaf. Much slower, since every leaf has to be played and checked for mate.
+pub fn perft_stats(board: &Board, depth: u32) -> PerftStats {
+    let mut stats = PerftStats::default();
+    if depth == 0 {
+        stats.nodes = 1;
+        return stats;
+    }
+    for m in legal_moves(board) {
+        let after = board.with_move(m);
+        if depth > 1 {
+            stats += perft_stats(&after, depth - 1);
+            continue;
+        }
+        stats.nodes += 1;
+        let Move::Normal {from, to, promotion} = m else {
+            continue;
+        };
+        let moved = board.piece_at(from).map(|piece| piece.kind);
+        if moved == Some(PieceKind::Pawn) && Some(to) == board.en_passant() {
+            stats.en_passants += 1;
+            stats.captures += 1;
+        } else if board.piece_at(to).is_some() {
+            stats.captures += 1;
+        }
+        if moved == Some(PieceKind::King) && from.file().abs_diff(to.file()) == 2 {
+            stats.castles += 1;
+        }
+        if promotion.is_some() {
+            stats.promotions += 1;
+        }
+        if after.in_check() {
+            stats.checks += 1;
+            if legal_moves(&after).is_empty() {
+                stats.checkmates += 1;
+            }
+        }
+    }
+    stats
+}
+
+/// Runs perft separately after each legal move, which makes it easy to find which move a buggy generator gets wrong by comparing against another engine.
+/// For depth 1 and up, the counts add up to perft(board, depth). Depth 0 plays no moves, so there's nothing to split and the list is empty.
+pub fn divide(board: &Board, depth: u32) -> Vec<(Move, u64)> {
+    if depth == 0 {
+        return Vec::new();
+    }
+    legal_moves(board).into_iter().map(|m| {
+        (m, perft(&board.with_move(m), depth - 1))
+    }).collect()
+}
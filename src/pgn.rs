@@ -0,0 +1,366 @@
+//! Reading and writing games in PGN (Portable Game Notation), the usual format for storing and sharing chess games.
+//! See http://www.saremba.de/chessgml/standards/pgn/pgn-complete.htm for the standard.
+
+use crate::board::{Board, Color, FenError, START_FEN};
+use crate::game::{Game, IllegalMove, Outcome};
+use crate::san::{line_to_san, parse_san, SanError};
+use crate::uci::Move;
+
+/// The result recorded at the end of a game's moves, and in its Result tag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PgnResult {
+    WhiteWins,
+    BlackWins,
+    Draw,
+    /// The game is still going, or the result isn't known. Written as "*".
+    #[default]
+    Unknown
+}
+
+impl PgnResult {
+    fn from_token(token: &str) -> Option<PgnResult> {
+        match token {
+            "1-0" => Some(PgnResult::WhiteWins),
+            "0-1" => Some(PgnResult::BlackWins),
+            "1/2-1/2" => Some(PgnResult::Draw),
+            "*" => Some(PgnResult::Unknown),
+            _ => None
+        }
+    }
+}
+
+impl From<Option<Outcome>> for PgnResult {
+    fn from(outcome: Option<Outcome>) -> Self {
+        match outcome.map(Outcome::winner) {
+            None => PgnResult::Unknown,
+            Some(None) => PgnResult::Draw,
+            Some(Some(Color::White)) => PgnResult::WhiteWins,
+            Some(Some(Color::Black)) => PgnResult::BlackWins
+        }
+    }
+}
+
+impl std::fmt::Display for PgnResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PgnResult::WhiteWins => write!(f, "1-0"),
+            PgnResult::BlackWins => write!(f, "0-1"),
+            PgnResult::Draw => write!(f, "1/2-1/2"),
+            PgnResult::Unknown => write!(f, "*")
+        }
+    }
+}
+
+/// Describes why some PGN couldn't be read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PgnError {
+    /// A tag pair wasn't a name followed by a quoted value. Holds the text between the brackets.
+    InvalidTag(String),
+    /// The FEN tag didn't hold a valid position.
+    InvalidFen(FenError),
+    /// A move couldn't be played. The ply counts from 1 for the first move of the game.
+    InvalidMove {ply: usize, error: SanError},
+    /// A comment or tag was still open at the end of the text.
+    Unterminated(char),
+    /// A variation was closed without being opened, or left open at the end of the game.
+    UnbalancedVariation
+}
+
+impl std::fmt::Display for PgnError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PgnError::InvalidTag(tag) => write!(f, "[{tag}] is not a valid tag pair"),
+            PgnError::InvalidFen(error) => write!(f, "invalid FEN tag: {error}"),
+            PgnError::InvalidMove {ply, error} => write!(f, "move {ply}: {error}"),
+            PgnError::Unterminated(c) => write!(f, "'{c}' is never closed"),
+            PgnError::UnbalancedVariation => write!(f, "the variations' brackets don't match up")
+        }
+    }
+}
+
+impl std::error::Error for PgnError {}
+
+impl From<FenError> for PgnError {
+    fn from(error: FenError) -> Self {
+        PgnError::InvalidFen(error)
+    }
+}
+
+/// The tags every PGN game has, in the order they're written. Missing ones are written with "?" as their value.
+const SEVEN_TAG_ROSTER: [&str; 7] = ["Event", "Site", "Date", "Round", "White", "Black", "Result"];
+
+/// Exported PGN keeps lines below 80 characters.
+const LINE_LENGTH: usize = 79;
+
+/// One game's worth of PGN: its tags, where it started and the moves of its main line.
+/// Comments, annotations and variations are skipped when reading, so they aren't kept.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PgnGame {
+    /// Tag names and values, in the order they were read or set.
+    pub tags: Vec<(String, String)>,
+    /// The position from the FEN tag, or the normal starting position if there isn't one.
+    pub start: Board,
+    pub moves: Vec<Move>,
+    pub result: PgnResult
+}
+
+impl PgnGame {
+    /// Makes the PGN for a game, with its result filled in from the game's outcome.
+    /// The tags are left for the caller to set. The Result tag is always written from `result`, so doesn't need setting.
+    pub fn new(game: &Game) -> PgnGame {
+        PgnGame {tags: Vec::new(), start: game.start().clone(), moves: game.moves().to_vec(), result: game.outcome().into()}
+    }
+
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags.iter().find(|(tag, _)| tag == name).map(|(_, value)| value.as_str())
+    }
+
+    /// Replaces the tag's value, or adds the tag if it isn't there yet.
+    pub fn set_tag(&mut self, name: &str, value: &str) {
+        match self.tags.iter_mut().find(|(tag, _)| tag == name) {
+            Some((_, existing)) => *existing = value.to_string(),
+            None => self.tags.push((name.to_string(), value.to_string()))
+        }
+    }
+
+    /// Replays the moves into a Game. Moves read from PGN are always legal, but `moves` may have been changed since.
+    pub fn game(&self) -> Result<Game, IllegalMove> {
+        let mut game = Game::new(self.start.clone());
+        for &m in &self.moves {
+            game.play(m)?;
+        }
+        Ok(game)
+    }
+}
+
+/// Writes the game in PGN export format: the seven tag roster first, then any other tags, then the moves wrapped to fit in 80 columns.
+/// SetUp and FEN tags are added when the game doesn't start from the normal starting position.
+impl std::fmt::Display for PgnGame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let escape = |value: &str| value.replace('\\', "\\\\").replace('"', "\\\"");
+        for name in SEVEN_TAG_ROSTER {
+            let value = match (name, self.tag(name)) {
+                ("Result", _) => self.result.to_string(),
+                (_, Some(value)) => value.to_string(),
+                ("Date", None) => "????.??.??".to_string(),
+                (_, None) => "?".to_string()
+            };
+            writeln!(f, "[{name} \"{}\"]", escape(&value))?;
+        }
+        let custom_start = self.start.to_fen() != START_FEN;
+        for (name, value) in &self.tags {
+            // SetUp and FEN are written from the start position instead, so they can't disagree with it
+            let generated = SEVEN_TAG_ROSTER.contains(&name.as_str()) || name == "SetUp" || name == "FEN";
+            if !generated {
+                writeln!(f, "[{name} \"{}\"]", escape(value))?;
+            }
+        }
+        if custom_start {
+            writeln!(f, "[SetUp \"1\"]")?;
+            writeln!(f, "[FEN \"{}\"]", self.start.to_fen())?;
+        }
+        writeln!(f)?;
+
+        let mut tokens = Vec::new();
+        let mut number = self.start.fullmove_number();
+        let mut color = self.start.side_to_move();
+        for (index, san) in line_to_san(&self.start, &self.moves).into_iter().enumerate() {
+            match color {
+                Color::White => tokens.push(format!("{number}.")),
+                // A game that starts with black to move needs the number before black's first move
+                Color::Black if index == 0 => tokens.push(format!("{number}...")),
+                Color::Black => ()
+            }
+            tokens.push(san);
+            if color == Color::Black {
+                number += 1;
+            }
+            color = color.opponent();
+        }
+        tokens.push(self.result.to_string());
+
+        let mut line = String::new();
+        for token in tokens {
+            if !line.is_empty() && line.len() + 1 + token.len() > LINE_LENGTH {
+                writeln!(f, "{line}")?;
+                line.clear();
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(&token);
+        }
+        writeln!(f, "{line}")
+    }
+}
+
+/// The pieces PGN text is made of, with comments already dropped.
+enum Token {
+    Tag(String, String),
+    /// A move, move number, annotation or result.
+    Symbol(String),
+    StartVariation,
+    EndVariation
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>, PgnError> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+    let mut line_start = true;
+    while let Some(c) = chars.next() {
+        let at_line_start = line_start;
+        line_start = c == '\n';
+        match c {
+            _ if c.is_whitespace() => (),
+            // Lines starting with % are escaped, meant for other programs
+            '%' if at_line_start => {
+                chars.by_ref().find(|&c| c == '\n');
+                line_start = true;
+            },
+            ';' => {
+                chars.by_ref().find(|&c| c == '\n');
+                line_start = true;
+            },
+            '{' => {
+                chars.by_ref().find(|&c| c == '}').ok_or(PgnError::Unterminated('{'))?;
+            },
+            '(' => tokens.push(Token::StartVariation),
+            ')' => tokens.push(Token::EndVariation),
+            '[' => {
+                let mut inside = String::new();
+                let mut in_quotes = false;
+                loop {
+                    match chars.next() {
+                        None => return Err(PgnError::Unterminated('[')),
+                        Some(']') if !in_quotes => break,
+                        Some('"') => {
+                            in_quotes = !in_quotes;
+                            inside.push('"');
+                        },
+                        // Backslash escapes a quote or another backslash inside the value
+                        Some('\\') if in_quotes => {
+                            inside.push('\\');
+                            inside.extend(chars.next());
+                        },
+                        Some(c) => inside.push(c)
+                    }
+                }
+                tokens.push(parse_tag(&inside)?);
+            },
+            _ => {
+                let mut symbol = c.to_string();
+                while let Some(&next) = chars.peek() {
+                    if next.is_whitespace() || "{}()[];".contains(next) {
+                        break;
+                    }
+                    symbol.push(next);
+                    chars.next();
+                }
+                tokens.push(Token::Symbol(symbol));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+fn parse_tag(inside: &str) -> Result<Token, PgnError> {
+    let invalid = || PgnError::InvalidTag(inside.to_string());
+    let (name, value) = inside.trim().split_once(char::is_whitespace).ok_or_else(invalid)?;
+    let value = value.trim().strip_prefix('"').and_then(|value| value.strip_suffix('"')).ok_or_else(invalid)?;
+    if name.is_empty() {
+        return Err(invalid());
+    }
+    let mut unescaped = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => unescaped.extend(chars.next()),
+            _ => unescaped.push(c)
+        }
+    }
+    Ok(Token::Tag(name.to_string(), unescaped))
+}
+
+/// A game being read, before its start position is known for certain.
+#[derive(Default)]
+struct PartialGame {
+    tags: Vec<(String, String)>,
+    moves: Vec<String>,
+    result: Option<PgnResult>
+}
+
+impl PartialGame {
+    fn is_empty(&self) -> bool {
+        self.tags.is_empty() && self.moves.is_empty() && self.result.is_none()
+    }
+
+    fn finish(self) -> Result<PgnGame, PgnError> {
+        let start = match self.tags.iter().find(|(name, _)| name == "FEN") {
+            Some((_, fen)) => Board::from_fen(fen)?,
+            None => Board::start_position()
+        };
+        let mut board = start.clone();
+        let mut moves = Vec::with_capacity(self.moves.len());
+        for (index, san) in self.moves.iter().enumerate() {
+            let m = parse_san(&board, san).map_err(|error| PgnError::InvalidMove {ply: index + 1, error})?;
+            // parse_san reads "--" as a null move, which PGN doesn't allow in a game
+            if m == Move::Null {
+                return Err(PgnError::InvalidMove {ply: index + 1, error: SanError::IllegalMove(san.clone())});
+            }
+            board.play(m);
+            moves.push(m);
+        }
+        // A game without a result at the end of its moves falls back on its Result tag
+        let result = self.result
+            .or_else(|| self.tags.iter().find(|(name, _)| name == "Result").and_then(|(_, value)| PgnResult::from_token(value)))
+            .unwrap_or_default();
+        Ok(PgnGame {tags: self.tags, start, moves, result})
+    }
+}
+
+/// Reads every game in some PGN text.
+/// Comments, NAGs and annotations are skipped, and so are variations, however deeply nested: only the main line is kept.
+/// Games are expected to end with a result, but a new set of tags or the end of the text also ends one.
+pub fn read_pgn(text: &str) -> Result<Vec<PgnGame>, PgnError> {
+    let mut games = Vec::new();
+    let mut game = PartialGame::default();
+    let mut depth = 0;
+    for token in tokenize(text)? {
+        match token {
+            Token::StartVariation => depth += 1,
+            Token::EndVariation if depth == 0 => return Err(PgnError::UnbalancedVariation),
+            Token::EndVariation => depth -= 1,
+            _ if depth > 0 => (),
+            Token::Tag(name, value) => {
+                if !game.moves.is_empty() || game.result.is_some() {
+                    games.push(std::mem::take(&mut game).finish()?);
+                }
+                game.tags.push((name, value));
+            },
+            Token::Symbol(symbol) => {
+                if let Some(result) = PgnResult::from_token(&symbol) {
+                    game.result = Some(result);
+                    games.push(std::mem::take(&mut game).finish()?);
+                    continue;
+                }
+                if symbol.starts_with('$') {
+                    continue;
+                }
+                // Move numbers can be written as "12.", "12..." or stuck to the move as in "12.e4"
+                let is_castling = symbol.starts_with("0-0");
+                let san = if is_castling { &symbol[..] } else { symbol.trim_start_matches(|c: char| c.is_ascii_digit() || c == '.') };
+                // Annotations and "e.p." can also stand apart from the move they're about
+                if !san.chars().all(|c| c == '!' || c == '?') && san != "e.p." && san != "ep" {
+                    game.moves.push(san.to_string());
+                }
+            }
+        }
+    }
+    if depth != 0 {
+        return Err(PgnError::UnbalancedVariation);
+    }
+    if !game.is_empty() {
+        games.push(game.finish()?);
+    }
+    Ok(games)
+}
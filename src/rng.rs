@@ -0,0 +1,57 @@
+use std::hash::{BuildHasher, Hasher};
+
+/// A small, fast pseudo random number generator (xorshift64*).
+/// Good enough for picking moves and breaking ties, but not for anything security related.
+/// The same seed always gives the same sequence, which makes games reproducible.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64
+}
+
+impl Rng {
+    /// Any seed is fine, including 0.
+    pub const fn new(seed: u64) -> Rng {
+        // xorshift gets stuck on a state of 0, and similar seeds give similar early outputs, so mix the seed first (splitmix64)
+        let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        Rng {state: if z == 0 { 0x9E37_79B9_7F4A_7C15 } else { z }}
+    }
+
+    /// Seeds the generator differently every time the program runs.
+    pub fn from_entropy() -> Rng {
+        // std doesn't expose an entropy source directly, but it does randomly seed its hash maps
+        let seed = std::collections::hash_map::RandomState::new().build_hasher().finish();
+        Rng::new(seed)
+    }
+
+    pub const fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// Returns a uniformly distributed number in 0..bound. Panics if bound is 0.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        assert!(bound > 0, "bound must be positive");
+        // Reject the top few values that would make smaller results slightly more likely
+        let zone = u64::MAX - (u64::MAX % bound);
+        loop {
+            let value = self.next_u64();
+            if value < zone {
+                return value % bound;
+            }
+        }
+    }
+
+    /// Picks a uniformly random item, or None if there aren't any.
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+        if items.is_empty() {
+            None
+        } else {
+            items.get(self.below(items.len() as u64) as usize)
+        }
+    }
+}
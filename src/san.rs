@@ -0,0 +1,207 @@
+//! Standard Algebraic Notation, the short move notation people, PGN files and puzzle collections use: Nf3, exd5, O-O, e8=Q+.
+//! Unlike UCI's long algebraic notation, SAN only makes sense for a particular position, since it leaves out where the piece came from whenever that's clear.
+
+use crate::board::{Board, PieceKind};
+use crate::movegen::legal_moves;
+use crate::uci::{Move, PromotionPiece, Square};
+
+/// Describes why a SAN move couldn't be matched to a legal move.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SanError {
+    /// The text isn't a move in any notation this understands. Holds the offending text.
+    Invalid(String),
+    /// The move is well formed, but no legal move fits it.
+    IllegalMove(String),
+    /// More than one legal move fits, so the move needs to say which piece moves.
+    Ambiguous(String)
+}
+
+impl std::fmt::Display for SanError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SanError::Invalid(san) => write!(f, "\"{san}\" is not a move"),
+            SanError::IllegalMove(san) => write!(f, "{san} is not a legal move"),
+            SanError::Ambiguous(san) => write!(f, "{san} could be more than one move")
+        }
+    }
+}
+
+impl std::error::Error for SanError {}
+
+fn piece_letter(kind: PieceKind) -> char {
+    match kind {
+        PieceKind::Pawn => 'P',
+        PieceKind::Knight => 'N',
+        PieceKind::Bishop => 'B',
+        PieceKind::Rook => 'R',
+        PieceKind::Queen => 'Q',
+        PieceKind::King => 'K'
+    }
+}
+
+fn promotion_kind(piece: PromotionPiece) -> PieceKind {
+    match piece {
+        PromotionPiece::Knight => PieceKind::Knight,
+        PromotionPiece::Bishop => PieceKind::Bishop,
+        PromotionPiece::Rook => PieceKind::Rook,
+        PromotionPiece::Queen => PieceKind::Queen
+    }
+}
+
+/// Writes a move in SAN, which must be legal in the position. Null moves are written as "--", as many PGN tools do.
+/// Includes the + or # suffix for check and mate, but never "e.p." for en passant, as the standard leaves it out.
+pub fn to_san(board: &Board, m: Move) -> String {
+    let Move::Normal {from, to, promotion} = m else {
+        return "--".to_string();
+    };
+    let piece = board.piece_at(from).expect("a legal move starts on an occupied square");
+    let mut san = String::new();
+
+    if piece.kind == PieceKind::King && from.file().abs_diff(to.file()) == 2 {
+        san.push_str(if to.file() > from.file() { "O-O" } else { "O-O-O" });
+    } else if piece.kind == PieceKind::Pawn {
+        // A pawn that changes file is always capturing, even en passant onto an empty square
+        if from.file() != to.file() {
+            san.push((b'a' + from.file()) as char);
+            san.push('x');
+        }
+        san.push_str(&to.to_string());
+        if let Some(promotion) = promotion {
+            san.push('=');
+            san.push(piece_letter(promotion_kind(promotion)));
+        }
+    } else {
+        san.push(piece_letter(piece.kind));
+        // Other pieces of the same kind that could also move there
+        let rivals: Vec<Square> = legal_moves(board).into_iter().filter_map(|other| match other {
+            Move::Normal {from: other_from, to: other_to, ..} if other_to == to && other_from != from
+                && board.piece_at(other_from) == Some(piece) => Some(other_from),
+            _ => None
+        }).collect();
+        if !rivals.is_empty() {
+            // Prefer the file, then the rank, and only give both if neither is enough on its own
+            let file_unique = rivals.iter().all(|rival| rival.file() != from.file());
+            let rank_unique = rivals.iter().all(|rival| rival.rank() != from.rank());
+            if file_unique {
+                san.push((b'a' + from.file()) as char);
+            } else if rank_unique {
+                san.push((b'1' + from.rank()) as char);
+            } else {
+                san.push_str(&from.to_string());
+            }
+        }
+        if board.piece_at(to).is_some() {
+            san.push('x');
+        }
+        san.push_str(&to.to_string());
+    }
+
+    let after = board.with_move(m);
+    if after.in_check() {
+        san.push(if legal_moves(&after).is_empty() { '#' } else { '+' });
+    }
+    san
+}
+
+/// Writes a sequence of moves played one after another from the position, each in SAN.
+pub fn line_to_san(board: &Board, moves: &[Move]) -> Vec<String> {
+    let mut board = board.clone();
+    moves.iter().map(|&m| {
+        let san = to_san(&board, m);
+        board.play(m);
+        san
+    }).collect()
+}
+
+/// Finds the legal move a SAN string describes.
+/// Lenient about what it accepts: check, mate and annotation marks are ignored, as are "e.p.", missing or extra 'x's and '='s, dashes between squares, 0-0 for O-O, lowercase piece letters other than b (which is always the b file), and long forms like Ng1f3.
+/// UCI moves like g1f3 are accepted too, since they're just SAN with every square given.
+pub fn parse_san(board: &Board, san: &str) -> Result<Move, SanError> {
+    let invalid = || SanError::Invalid(san.to_string());
+    let mut text = san.trim().trim_end_matches(['+', '#', '!', '?']).trim_end();
+    for suffix in ["e.p.", "ep"] {
+        text = text.strip_suffix(suffix).unwrap_or(text).trim_end();
+    }
+    if text.is_empty() {
+        return Err(invalid());
+    }
+    if text == "--" || text == "0000" {
+        return Ok(Move::Null);
+    }
+
+    let legal = legal_moves(board);
+    let castling = match text {
+        "O-O" | "0-0" | "o-o" => Some(6),
+        "O-O-O" | "0-0-0" | "o-o-o" => Some(2),
+        _ => None
+    };
+    if let Some(file) = castling {
+        // The only legal moves that take the king two files along are castling
+        let king = board.king_square(board.side_to_move());
+        let m = Move::new(king, Square::new(file, king.rank()));
+        if king.file() == 4 && legal.contains(&m) {
+            return Ok(m);
+        }
+        return Err(SanError::IllegalMove(san.to_string()));
+    }
+
+    let mut chars: Vec<char> = text.chars().filter(|c| !matches!(c, 'x' | 'X' | ':' | '-' | '=')).collect();
+
+    let promotion = match chars.last().and_then(|c| PromotionPiece::from_char(c.to_ascii_lowercase())) {
+        // The b file can't end a move, so a trailing b is a bishop promotion
+        Some(piece) if chars.len() >= 3 && chars[chars.len() - 2].is_ascii_digit() => {
+            chars.pop();
+            Some(piece)
+        },
+        _ => None
+    };
+
+    let kind = match chars.first() {
+        Some('N' | 'n') => Some(PieceKind::Knight),
+        Some('B') => Some(PieceKind::Bishop),
+        Some('R' | 'r') => Some(PieceKind::Rook),
+        Some('Q' | 'q') => Some(PieceKind::Queen),
+        Some('K' | 'k') => Some(PieceKind::King),
+        Some('P' | 'p') => Some(PieceKind::Pawn),
+        _ => None
+    };
+    if kind.is_some() {
+        chars.remove(0);
+    }
+
+    // What's left is an optional file and rank to start from, then the square to move to
+    if chars.len() < 2 {
+        return Err(invalid());
+    }
+    let to: Square = chars[chars.len() - 2..].iter().collect::<String>().parse().map_err(|_| invalid())?;
+    let (mut from_file, mut from_rank) = (None, None);
+    for &c in &chars[..chars.len() - 2] {
+        match c {
+            'a'..='h' if from_file.is_none() && from_rank.is_none() => from_file = Some(c as u8 - b'a'),
+            '1'..='8' if from_rank.is_none() => from_rank = Some(c as u8 - b'1'),
+            _ => return Err(invalid())
+        }
+    }
+    // Without a piece letter it's a pawn move, unless the whole starting square is given like in UCI
+    let kind = match (kind, from_file, from_rank) {
+        (None, Some(_), Some(_)) => None,
+        (None, _, _) => Some(PieceKind::Pawn),
+        (kind, _, _) => kind
+    };
+
+    let mut matches = legal.into_iter().filter(|&m| {
+        let Move::Normal {from, to: target, promotion: m_promotion} = m else {
+            return false;
+        };
+        target == to
+            && m_promotion == promotion
+            && kind.is_none_or(|kind| board.piece_at(from).is_some_and(|piece| piece.kind == kind))
+            && from_file.is_none_or(|file| from.file() == file)
+            && from_rank.is_none_or(|rank| from.rank() == rank)
+    });
+    match (matches.next(), matches.next()) {
+        (Some(m), None) => Ok(m),
+        (None, _) => Err(SanError::IllegalMove(san.to_string())),
+        (Some(_), Some(_)) => Err(SanError::Ambiguous(san.to_string()))
+    }
+}
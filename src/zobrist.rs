@@ -0,0 +1,80 @@
+//! Zobrist keys: a random 64 bit number for each feature a position can have, xor-ed together to give a key for the whole position.
+//! Xor undoes itself, so a move can update the key by xor-ing out what it removes and xor-ing in what it adds.
+
+use crate::board::{CastlingRights, Piece};
+use crate::rng::Rng;
+use crate::uci::Square;
+
+struct Keys {
+    /// Indexed by color, then kind of piece, then square.
+    pieces: [[[u64; 64]; 6]; 2],
+    /// One key per combination of castling rights, each being the xor of the keys of the rights it contains.
+    castling: [u64; 16],
+    en_passant_files: [u64; 8],
+    black_to_move: u64
+}
+
+impl Keys {
+    const fn generate() -> Keys {
+        // The seed is arbitrary, but changing it changes every key, so anything stored with the old keys would stop matching
+        let mut rng = Rng::new(0x5EED_2017);
+        let mut pieces = [[[0; 64]; 6]; 2];
+        let mut color = 0;
+        while color < 2 {
+            let mut kind = 0;
+            while kind < 6 {
+                let mut square = 0;
+                while square < 64 {
+                    pieces[color][kind][square] = rng.next_u64();
+                    square += 1;
+                }
+                kind += 1;
+            }
+            color += 1;
+        }
+
+        let rights = [rng.next_u64(), rng.next_u64(), rng.next_u64(), rng.next_u64()];
+        let mut castling = [0; 16];
+        let mut bits = 0;
+        while bits < 16 {
+            let mut right = 0;
+            while right < 4 {
+                if bits & (1 << right) != 0 {
+                    castling[bits] ^= rights[right];
+                }
+                right += 1;
+            }
+            bits += 1;
+        }
+
+        let mut en_passant_files = [0; 8];
+        let mut file = 0;
+        while file < 8 {
+            en_passant_files[file] = rng.next_u64();
+            file += 1;
+        }
+
+        Keys {pieces, castling, en_passant_files, black_to_move: rng.next_u64()}
+    }
+}
+
+static KEYS: Keys = Keys::generate();
+
+pub fn piece(piece: Piece, square: Square) -> u64 {
+    KEYS.pieces[piece.color.index()][piece.kind.index()][square.index()]
+}
+
+pub fn castling(rights: CastlingRights) -> u64 {
+    KEYS.castling[rights.bits() as usize]
+}
+
+/// Only included in a position's key when the side to move has a pawn that could capture en passant.
+/// Otherwise positions that only differ by an unusable en passant square would get different keys, and wouldn't count as repetitions.
+pub fn en_passant_file(file: u8) -> u64 {
+    KEYS.en_passant_files[file as usize]
+}
+
+/// Included in the key when it's black's turn.
+pub fn black_to_move() -> u64 {
+    KEYS.black_to_move
+}
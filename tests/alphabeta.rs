@@ -0,0 +1,103 @@
+use std::time::{Duration, Instant};
+
+use chess::board::Board;
+use chess::engines::alphabeta::AlphaBeta;
+use chess::game::Game;
+use chess::movegen::legal_moves;
+use chess::uci::{BestMove, Engine, InfoCommandData, Move, ScoreInfoData, SearchLimits, SearchSignals};
+
+mod common;
+use common::uci;
+
+/// Searches a position, returning the move along with every info it reported.
+fn search(fen: &str, limits: SearchLimits, signals: &SearchSignals) -> (BestMove, Vec<InfoCommandData>) {
+    let mut engine = AlphaBeta::new();
+    engine.set_position(Game::new(Board::from_fen(fen).unwrap()));
+    let mut infos = Vec::new();
+    let best = engine.go(&limits, signals, &mut |reported| infos.extend(reported));
+    (best, infos)
+}
+
+fn depth(depth: usize) -> SearchLimits {
+    SearchLimits {depth: Some(depth), ..SearchLimits::default()}
+}
+
+fn last_score(infos: &[InfoCommandData]) -> Option<ScoreInfoData> {
+    infos.iter().rev().find_map(|info| match info {
+        InfoCommandData::Score(score) => Some(score.clone()),
+        _ => None
+    })
+}
+
+#[test]
+fn finds_a_mate_in_two() {
+    // Kb6 leaves the black king only b8, and then Rh8 mates
+    let (best, infos) = search("k7/8/2K5/8/8/8/8/7R w - - 0 1", depth(5), &SearchSignals::new());
+    assert_eq!(last_score(&infos), Some(ScoreInfoData::MateInMoves(2)));
+    assert!(legal_moves(&Board::from_fen("k7/8/2K5/8/8/8/8/7R w - - 0 1").unwrap()).contains(&best.selected_move));
+}
+
+#[test]
+fn mates_on_the_fiftieth_move() {
+    // Rh8 is the hundredth halfmove without a capture or pawn move, but mate ends the game before the draw can be claimed
+    let (best, infos) = search("k7/8/1K6/8/8/8/8/7R w - - 99 80", depth(2), &SearchSignals::new());
+    assert_eq!(best.selected_move, uci("h1h8"));
+    assert_eq!(last_score(&infos), Some(ScoreInfoData::MateInMoves(1)));
+}
+
+#[test]
+fn sees_a_defended_pawn() {
+    // The greedy mover would take on d5 and lose its queen
+    let (best, infos) = search("4k3/8/2p5/3p4/8/8/8/3QK3 w - - 0 1", depth(2), &SearchSignals::new());
+    assert_ne!(best.selected_move, uci("d1d5"));
+    assert_eq!(last_score(&infos), Some(ScoreInfoData::CentiPawns(700)));
+}
+
+#[test]
+fn reports_every_iteration() {
+    let (best, infos) = search(chess::board::START_FEN, depth(3), &SearchSignals::new());
+    let depths: Vec<usize> = infos.iter().filter_map(|info| match info {
+        InfoCommandData::Depth(depth) => Some(*depth),
+        _ => None
+    }).collect();
+    assert_eq!(depths, [1, 2, 3]);
+    let count = |reported: fn(&InfoCommandData) -> bool| infos.iter().filter(|info| reported(info)).count();
+    assert_eq!(count(|info| matches!(info, InfoCommandData::SelectiveDepth(_))), 3);
+    assert_eq!(count(|info| matches!(info, InfoCommandData::Score(_))), 3);
+    assert_eq!(count(|info| matches!(info, InfoCommandData::NodesSearched(_))), 3);
+    assert_eq!(count(|info| matches!(info, InfoCommandData::NodesPerSecond(_))), 3);
+    assert_eq!(count(|info| matches!(info, InfoCommandData::TimeSpentSearching(_))), 3);
+    assert_eq!(count(|info| matches!(info, InfoCommandData::PrincipleVariation(_))), 3);
+    let Some(InfoCommandData::PrincipleVariation(pv)) = infos.last() else {
+        panic!("the principal variation should come last");
+    };
+    assert_eq!(pv[0], best.selected_move);
+    assert_eq!(pv.get(1).copied(), best.ponder);
+}
+
+#[test]
+fn respects_node_and_time_limits() {
+    let limits = SearchLimits {nodes: Some(2000), ..SearchLimits::default()};
+    let (best, infos) = search(chess::board::START_FEN, limits, &SearchSignals::new());
+    assert_ne!(best.selected_move, Move::Null);
+    assert!(infos.iter().all(|info| !matches!(info, InfoCommandData::NodesSearched(nodes) if *nodes > 2000)));
+
+    let started = Instant::now();
+    let limits = SearchLimits {move_time: Some(Duration::from_millis(100)), ..SearchLimits::default()};
+    let (best, _) = search(chess::board::START_FEN, limits, &SearchSignals::new());
+    assert_ne!(best.selected_move, Move::Null);
+    assert!(started.elapsed() < Duration::from_millis(500), "{:?}", started.elapsed());
+}
+
+#[test]
+fn stop_ends_an_infinite_search() {
+    let signals = SearchSignals::new();
+    let stopper = signals.clone();
+    std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(50));
+        stopper.stop();
+    });
+    let limits = SearchLimits {infinite: true, ..SearchLimits::default()};
+    let (best, _) = search(chess::board::START_FEN, limits, &signals);
+    assert!(legal_moves(&Board::start_position()).contains(&best.selected_move));
+}
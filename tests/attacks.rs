@@ -0,0 +1,74 @@
+use chess::attacks::{classical, magic, Slider};
+use chess::bitboard::Bitboard;
+use chess::rng::Rng;
+use chess::uci::Square;
+
+fn squares() -> impl Iterator<Item = Square> {
+    (0..64).map(|index| Square::from_index(index).unwrap())
+}
+
+/// Random boards with a mix of densities, from nearly empty to nearly full.
+fn occupancies(rng: &mut Rng) -> Vec<Bitboard> {
+    let mut boards = vec![Bitboard::EMPTY, Bitboard::FULL];
+    for _ in 0..500 {
+        boards.push(Bitboard(rng.next_u64() & rng.next_u64() & rng.next_u64()));
+        boards.push(Bitboard(rng.next_u64() & rng.next_u64()));
+        boards.push(Bitboard(rng.next_u64()));
+        boards.push(Bitboard(rng.next_u64() | rng.next_u64()));
+    }
+    boards
+}
+
+#[test]
+fn magic_attacks_match_ray_scan() {
+    let mut rng = Rng::new(0);
+    let boards = occupancies(&mut rng);
+    for slider in [Slider::Rook, Slider::Bishop] {
+        for square in squares() {
+            for &occupied in &boards {
+                assert_eq!(
+                    magic::slider_attacks(slider, square, occupied),
+                    classical::slider_attacks(slider, square, occupied),
+                    "{slider:?} on {square} with {occupied:?}"
+                );
+            }
+        }
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[test]
+fn pext_attacks_match_ray_scan() {
+    use chess::attacks::pext;
+    if !pext::is_supported() {
+        return;
+    }
+    let mut rng = Rng::new(1);
+    let boards = occupancies(&mut rng);
+    for slider in [Slider::Rook, Slider::Bishop] {
+        for square in squares() {
+            for &occupied in &boards {
+                assert_eq!(
+                    pext::slider_attacks(slider, square, occupied),
+                    classical::slider_attacks(slider, square, occupied),
+                    "{slider:?} on {square} with {occupied:?}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn relevant_occupancy_sizes() {
+    // Well known: a rook in the corner has 12 relevant squares, and a bishop in the centre has 9
+    assert_eq!(magic::relevant_occupancy(Slider::Rook, "a1".parse().unwrap()).count(), 12);
+    assert_eq!(magic::relevant_occupancy(Slider::Rook, "d4".parse().unwrap()).count(), 10);
+    assert_eq!(magic::relevant_occupancy(Slider::Bishop, "a1".parse().unwrap()).count(), 6);
+    assert_eq!(magic::relevant_occupancy(Slider::Bishop, "d4".parse().unwrap()).count(), 9);
+}
+
+#[test]
+#[cfg(feature = "magic")]
+fn magic_feature_takes_precedence() {
+    assert_eq!(chess::attacks::BACKEND, "magic");
+}
@@ -0,0 +1,25 @@
+//! Helpers shared by the integration tests. Each test file only uses some of them.
+#![allow(dead_code)]
+
+use chess::board::Board;
+use chess::game::Game;
+use chess::uci::Move;
+
+/// Reads a single move in UCI notation, like e2e4.
+pub fn uci(m: &str) -> Move {
+    m.parse().unwrap()
+}
+
+/// Reads a space separated list of moves in UCI notation.
+pub fn line(moves: &str) -> Vec<Move> {
+    moves.split_whitespace().map(uci).collect()
+}
+
+/// Plays the moves, given in UCI notation, from the position.
+pub fn game(fen: &str, moves: &str) -> Game {
+    let mut game = Game::new(Board::from_fen(fen).unwrap());
+    for m in line(moves) {
+        game.play(m).unwrap();
+    }
+    game
+}
@@ -0,0 +1,97 @@
+use chess::board::Board;
+use chess::game::Game;
+use chess::history::History;
+
+mod common;
+use common::game;
+
+fn start(moves: &str) -> Game {
+    game(chess::board::START_FEN, moves)
+}
+
+#[test]
+fn counts_repetitions() {
+    let shuffle = "g1f3 g8f6 f3g1 f6g8";
+    assert_eq!(start("").repetitions(), 1);
+    assert_eq!(start(shuffle).repetitions(), 2);
+    assert!(!start(shuffle).is_threefold_repetition());
+    let twice = format!("{shuffle} {shuffle}");
+    assert!(start(&twice).is_threefold_repetition());
+    assert!(!start(&twice).is_fivefold_repetition());
+    let four_times = format!("{twice} {twice}");
+    assert!(start(&four_times).is_fivefold_repetition());
+    // Same pieces, but the other side to move
+    assert_eq!(start("g1f3 g8f6 f3g1 f6g8 g1f3").repetitions(), 2);
+    assert_eq!(start("g1f3 g8f6 f3g1").repetitions(), 1);
+}
+
+#[test]
+fn lost_castling_rights_make_a_new_position() {
+    // After the kings step out and back, the pieces are where they started but castling is gone
+    let game = start("e2e4 e7e5 e1e2 e8e7 e2e1 e7e8");
+    assert_eq!(game.repetitions(), 1);
+    let game = start("e2e4 e7e5 e1e2 e8e7 e2e1 e7e8 e1e2 e8e7 e2e1 e7e8");
+    assert_eq!(game.repetitions(), 2);
+}
+
+#[test]
+fn search_history_finds_twofold_repetitions() {
+    let game = start("g1f3 g8f6");
+    let mut history = History::from_game(&game);
+    let mut board = game.board().clone();
+    board.play("f3g1".parse().unwrap());
+    history.push(board.key());
+    assert!(!history.is_repetition(board.halfmove_clock()));
+    // Back to the starting position, which is in the game's part of the history
+    board.play("f6g8".parse().unwrap());
+    history.push(board.key());
+    assert!(history.is_repetition(board.halfmove_clock()));
+    history.pop();
+    assert!(!history.is_repetition(3));
+}
+
+#[test]
+fn irreversible_moves_reset_the_window() {
+    // The halfmove clock says nothing before the latest position can repeat, even though the keys match
+    let mut history = History::new();
+    history.push(1);
+    history.push(2);
+    history.push(1);
+    assert_eq!(history.occurrences(2), 2);
+    assert_eq!(history.occurrences(0), 1);
+}
+
+#[test]
+fn move_rules() {
+    let fen = |clock: u32| format!("4k3/8/8/8/8/8/8/R3K3 w - - {clock} 80");
+    assert!(!game(&fen(99), "").is_fifty_move_rule());
+    assert!(game(&fen(99), "a1a2").is_fifty_move_rule());
+    assert!(!game(&fen(149), "").is_seventy_five_move_rule());
+    assert!(game(&fen(149), "a1a2").is_seventy_five_move_rule());
+}
+
+#[test]
+fn insufficient_material() {
+    let insufficient = [
+        "4k3/8/8/8/8/8/8/4K3 w - - 0 1",
+        "4k3/8/8/8/8/8/8/4KN2 w - - 0 1",
+        "4kb2/8/8/8/8/8/8/4K3 w - - 0 1",
+        // Bishops all on light squares, whoever they belong to
+        "2b1k3/8/8/8/8/8/8/3BK3 w - - 0 1",
+        "4k3/8/8/8/8/8/8/1B1BKB2 w - - 0 1"
+    ];
+    for fen in insufficient {
+        assert!(Board::from_fen(fen).unwrap().has_insufficient_material(), "{fen}");
+    }
+    let sufficient = [
+        "4k3/8/8/8/8/8/4P3/4K3 w - - 0 1",
+        "4k3/8/8/8/8/8/8/4KR2 w - - 0 1",
+        "4k3/8/8/8/8/8/8/3NKN2 w - - 0 1",
+        "4kn2/8/8/8/8/8/8/4KB2 w - - 0 1",
+        // Bishops on both colors
+        "4kb2/8/8/8/8/8/8/4KB2 w - - 0 1"
+    ];
+    for fen in sufficient {
+        assert!(!Board::from_fen(fen).unwrap().has_insufficient_material(), "{fen}");
+    }
+}
@@ -0,0 +1,42 @@
+use chess::board::Board;
+use chess::epd::{read_epd, Epd, EpdError};
+
+mod common;
+use common::uci;
+
+#[test]
+fn reads_a_test_suite_line() {
+    // WAC.001
+    let epd: Epd = r#"2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - bm Qg6; id "WAC.001";"#.parse().unwrap();
+    assert_eq!(epd.board, Board::from_fen("2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - 0 1").unwrap());
+    assert_eq!(epd.id(), Some("WAC.001"));
+    assert_eq!(epd.best_moves(), Ok(vec![uci("g3g6")]));
+    assert_eq!(epd.avoid_moves(), Ok(vec![]));
+    assert_eq!(epd.operation("bm"), Some(&["Qg6".to_string()][..]));
+}
+
+#[test]
+fn reads_operands_and_quoted_strings() {
+    let epd: Epd = r#"4k3/8/8/8/8/8/4P3/4K3 w - - am e3 Kd1; c0 "quiet; really"; dm 3; hmvc 12; fmvn 40"#.parse().unwrap();
+    assert_eq!(epd.avoid_moves(), Ok(vec![uci("e2e3"), uci("e1d1")]));
+    assert_eq!(epd.comment(0), Some("quiet; really"));
+    assert_eq!(epd.comment(1), None);
+    assert_eq!(epd.direct_mate(), Some(3));
+    assert_eq!(epd.board.to_fen(), "4k3/8/8/8/8/8/4P3/4K3 w - - 12 40");
+}
+
+#[test]
+fn rejects_bad_lines() {
+    assert_eq!("4k3/8/8/8/8/8/8/4K3 w -".parse::<Epd>(), Err(EpdError::MissingFields));
+    assert!(matches!("4k3/8/8/8/8/8/8/4K3 w - - 3".parse::<Epd>(), Err(EpdError::InvalidOperation(_))));
+    assert_eq!(r#"4k3/8/8/8/8/8/8/4K3 w - - id "open"#.parse::<Epd>(), Err(EpdError::UnterminatedString));
+    assert!(matches!("4k3/8/8/8/8/8/8/4KK2 w - - id x;".parse::<Epd>(), Err(EpdError::InvalidFen(_))));
+}
+
+#[test]
+fn reads_a_file() {
+    let text = "# a comment\n\n4k3/8/8/8/8/8/8/4K3 w - - id \"one\";\n4k3/8/8/8/8/8/8/4K3 b - - id \"two\";\n";
+    let positions = read_epd(text).unwrap();
+    assert_eq!(positions.iter().map(|epd| epd.id().unwrap()).collect::<Vec<_>>(), ["one", "two"]);
+    assert_eq!(read_epd("4k3/8/8/8/8/8/8/4K3 w - - id x;\nnonsense\n").map(|_| ()), Err((2, EpdError::MissingFields)));
+}
@@ -0,0 +1,62 @@
+use chess::board::{Board, Color, FenError, START_FEN};
+
+fn error(fen: &str) -> FenError {
+    Board::from_fen(fen).unwrap_err()
+}
+
+fn square(name: &str) -> chess::uci::Square {
+    name.parse().unwrap()
+}
+
+#[test]
+fn round_trips() {
+    for fen in [
+        START_FEN,
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 b - - 17 42"
+    ] {
+        assert_eq!(Board::from_fen(fen).unwrap().to_fen(), fen);
+    }
+    // The clocks may be left out
+    assert_eq!(Board::from_fen("4k3/8/8/8/8/8/8/4K3 w - -").unwrap().to_fen(), "4k3/8/8/8/8/8/8/4K3 w - - 0 1");
+}
+
+#[test]
+fn rejects_malformed_fields() {
+    assert_eq!(error("4k3/8/8/8/8/8/8/4K3 w"), FenError::WrongFieldCount(2));
+    assert_eq!(error("4k3/8/8/8/8/8/4K3 w - - 0 1"), FenError::WrongRankCount(7));
+    assert_eq!(error("4k3/8/8/8/8/8/8/4K4 w - - 0 1"), FenError::WrongRankLength {rank: 1, length: 9});
+    assert_eq!(error("4k3/8/8/8/8/8/8/4X3 w - - 0 1"), FenError::InvalidPiece('X'));
+    assert_eq!(error("4k3/8/8/8/8/8/8/4K3 x - - 0 1"), FenError::InvalidSideToMove("x".to_string()));
+}
+
+#[test]
+fn needs_one_king_each() {
+    assert_eq!(error("4k3/8/8/8/8/8/8/3KK3 w - - 0 1"), FenError::WrongKingCount {color: Color::White, count: 2});
+    assert_eq!(error("8/8/8/8/8/8/8/4K3 w - - 0 1"), FenError::WrongKingCount {color: Color::Black, count: 0});
+}
+
+#[test]
+fn rejects_impossible_positions() {
+    assert_eq!(error("4k2P/8/8/8/8/8/8/4K3 w - - 0 1"), FenError::PawnOnBackRank(square("h8")));
+    assert_eq!(error("4k3/8/8/8/8/8/8/p3K3 w - - 0 1"), FenError::PawnOnBackRank(square("a1")));
+    // Black is in check from the rook, but it's white's move
+    assert_eq!(error("4k3/8/8/8/8/8/8/4RK2 w - - 0 1"), FenError::OpponentInCheck);
+}
+
+#[test]
+fn castling_needs_the_king_and_rook_in_place() {
+    assert_eq!(error("r3k2r/8/8/8/8/8/8/R3K1R1 w K - 0 1"), FenError::CastlingWithoutPieces('K'));
+    assert_eq!(error("r3k2r/8/8/8/8/8/8/R2K3R w Q - 0 1"), FenError::CastlingWithoutPieces('Q'));
+    assert_eq!(error("1r2k2r/8/8/8/8/8/8/R3K2R w q - 0 1"), FenError::CastlingWithoutPieces('q'));
+}
+
+#[test]
+fn en_passant_needs_a_pawn_that_just_moved_two_squares() {
+    // No black pawn on e5
+    assert_eq!(error("4k3/8/8/8/8/8/8/4K3 w - e6 0 1"), FenError::ImpossibleEnPassant(square("e6")));
+    // The square is on the wrong rank for white to move
+    assert_eq!(error("4k3/8/8/8/4P3/8/8/4K3 w - e3 0 1"), FenError::ImpossibleEnPassant(square("e3")));
+    assert!(matches!(error("4k3/8/8/8/8/8/8/4K3 w - e9 0 1"), FenError::InvalidEnPassant(_)));
+}
@@ -0,0 +1,53 @@
+use chess::board::Board;
+use chess::engines::greedy::GreedyMover;
+use chess::game::Game;
+use chess::rng::Rng;
+use chess::uci::{Engine, InfoCommandData, Move, ScoreInfoData, SearchLimits, SearchSignals};
+
+mod common;
+use common::uci;
+
+/// Asks a greedy mover for a move, returning it along with the score it reported.
+fn go(fen: &str, limits: SearchLimits) -> (Move, Option<ScoreInfoData>) {
+    let mut engine = GreedyMover::new(Rng::new(0));
+    engine.set_position(Game::new(Board::from_fen(fen).unwrap()));
+    let mut score = None;
+    let best = engine.go(&limits, &SearchSignals::new(), &mut |infos| {
+        for info in infos {
+            if let InfoCommandData::Score(reported) = info {
+                score = Some(reported);
+            }
+        }
+    });
+    (best.selected_move, score)
+}
+
+#[test]
+fn takes_the_most_material() {
+    // The rook can take a knight or the queen
+    let (m, score) = go("3qk3/8/8/8/3R1n2/8/8/4K3 w - - 0 1", SearchLimits::default());
+    assert_eq!(m, uci("d4d8"));
+    assert_eq!(score, Some(ScoreInfoData::CentiPawns(200)));
+}
+
+#[test]
+fn prefers_mate_to_material() {
+    // Ra8 mates, while Rxh3 wins a knight
+    let (m, score) = go("6k1/5ppp/8/8/8/7n/8/R3K2R w - - 0 1", SearchLimits::default());
+    assert_eq!(m, uci("a1a8"));
+    assert_eq!(score, Some(ScoreInfoData::MateInMoves(1)));
+}
+
+#[test]
+fn avoids_stalemate_when_ahead() {
+    // Taking the rook leaves the black king with no moves
+    let (m, _) = go("7k/5K2/6r1/8/8/3Q4/8/8 w - - 0 1", SearchLimits::default());
+    assert_ne!(m, uci("d3g6"));
+}
+
+#[test]
+fn only_considers_search_moves() {
+    let limits = SearchLimits {search_moves: vec![uci("e1f1")], ..SearchLimits::default()};
+    let (m, _) = go("3qk3/8/8/8/3R4/8/8/4K3 w - - 0 1", limits);
+    assert_eq!(m, uci("e1f1"));
+}
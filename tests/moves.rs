@@ -0,0 +1,37 @@
+use chess::uci::{Move, MoveParseError, PromotionPiece, Square, SquareParseError};
+
+#[test]
+fn squares_round_trip() {
+    for name in ["a1", "e4", "h8"] {
+        assert_eq!(name.parse::<Square>().unwrap().to_string(), name);
+    }
+    assert_eq!("e4".parse::<Square>(), Ok(Square::new(4, 3)));
+}
+
+#[test]
+fn moves_round_trip() {
+    for text in ["e2e4", "e7e8q", "a2a1n", "0000"] {
+        assert_eq!(text.parse::<Move>().unwrap().to_string(), text);
+    }
+    assert_eq!("0000".parse::<Move>(), Ok(Move::Null));
+    assert_eq!("e7e8q".parse::<Move>(), Ok(Move::Normal {
+        from: Square::new(4, 6),
+        to: Square::new(4, 7),
+        promotion: Some(PromotionPiece::Queen)
+    }));
+}
+
+#[test]
+fn square_errors() {
+    assert_eq!("e".parse::<Square>(), Err(SquareParseError::WrongLength("e".to_string())));
+    assert_eq!("i4".parse::<Square>(), Err(SquareParseError::InvalidFile('i')));
+    assert_eq!("e9".parse::<Square>(), Err(SquareParseError::InvalidRank('9')));
+}
+
+#[test]
+fn move_errors() {
+    assert_eq!("e2e".parse::<Move>(), Err(MoveParseError::WrongLength("e2e".to_string())));
+    assert_eq!("e2e4qq".parse::<Move>(), Err(MoveParseError::WrongLength("e2e4qq".to_string())));
+    assert_eq!("e2z4".parse::<Move>(), Err(MoveParseError::InvalidSquare(SquareParseError::InvalidFile('z'))));
+    assert_eq!("e7e8k".parse::<Move>(), Err(MoveParseError::InvalidPromotion('k')));
+}
@@ -0,0 +1,61 @@
+use chess::uci::{EngineOptions, EngineParameter, OptionError, OptionValue};
+
+fn options() -> EngineOptions {
+    EngineOptions::new(vec![
+        ("Hash".to_string(), EngineParameter::Spin {default: 16, min: 1, max: 1024}),
+        ("Style".to_string(), EngineParameter::Combo {default: "Normal".to_string(), choices: vec!["Solid".to_string(), "Normal".to_string(), "Risky".to_string()]}),
+        ("Clear Hash".to_string(), EngineParameter::Button),
+        ("Log File".to_string(), EngineParameter::String("chess.log".to_string())),
+        ("Ponder".to_string(), EngineParameter::Check(false))
+    ])
+}
+
+#[test]
+fn starts_at_the_defaults() {
+    let options = options();
+    assert_eq!(options.get("Hash"), Some(&OptionValue::Spin(16)));
+    assert_eq!(options.get("style"), Some(&OptionValue::Combo("Normal".to_string())));
+    assert_eq!(options.get("Missing"), None);
+}
+
+#[test]
+fn checks_spin_ranges() {
+    let mut options = options();
+    assert_eq!(options.set("Hash", Some("1024")), Ok(("Hash".to_string(), OptionValue::Spin(1024))));
+    assert_eq!(options.set("Hash", Some("0")), Err(OptionError::OutOfRange {value: 0, min: 1, max: 1024}));
+    assert_eq!(options.set("Hash", Some("lots")), Err(OptionError::InvalidValue("lots".to_string())));
+    assert_eq!(options.set("Hash", None), Err(OptionError::MissingValue));
+    assert_eq!(options.get("Hash"), Some(&OptionValue::Spin(1024)));
+}
+
+#[test]
+fn matches_names_and_combo_choices_ignoring_case() {
+    let mut options = options();
+    assert_eq!(options.set("STYLE", Some("risky")), Ok(("Style".to_string(), OptionValue::Combo("Risky".to_string()))));
+    assert_eq!(options.set("Style", Some("Reckless")), Err(OptionError::InvalidValue("Reckless".to_string())));
+}
+
+#[test]
+fn buttons_take_no_value() {
+    let mut options = options();
+    assert_eq!(options.set("Clear Hash", None), Ok(("Clear Hash".to_string(), OptionValue::Button)));
+    assert_eq!(options.set("Clear Hash", Some("now")), Err(OptionError::InvalidValue("now".to_string())));
+}
+
+#[test]
+fn reads_empty_strings() {
+    let mut options = options();
+    assert_eq!(options.set("Log File", Some("<empty>")), Ok(("Log File".to_string(), OptionValue::String(String::new()))));
+    assert_eq!(options.set("Ponder", Some("yes")), Err(OptionError::InvalidValue("yes".to_string())));
+}
+
+#[test]
+fn rejects_unknown_options() {
+    assert_eq!(options().set("Threads", Some("4")), Err(OptionError::UnknownOption("Threads".to_string())));
+}
+
+#[test]
+#[should_panic(expected = "not between 1 and 1024")]
+fn spin_defaults_must_be_in_range() {
+    EngineOptions::new(vec![("Hash".to_string(), EngineParameter::Spin {default: 0, min: 1, max: 1024})]);
+}
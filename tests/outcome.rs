@@ -0,0 +1,50 @@
+use chess::board::Color;
+use chess::game::{DrawReason, Game, GameState, Outcome};
+
+mod common;
+use common::game;
+
+#[test]
+fn ongoing() {
+    assert_eq!(Game::default().state(), GameState::Ongoing);
+    assert_eq!(Game::default().outcome(), None);
+}
+
+#[test]
+fn checkmate() {
+    let fools_mate = game(chess::board::START_FEN, "f2f3 e7e5 g2g4 d8h4");
+    assert_eq!(fools_mate.outcome(), Some(Outcome::Checkmate {winner: Color::Black}));
+    assert_eq!(fools_mate.outcome().unwrap().winner(), Some(Color::Black));
+}
+
+#[test]
+fn checkmate_beats_the_seventy_five_move_rule() {
+    let mate = game("6k1/5ppp/8/8/8/8/8/R5K1 w - - 149 100", "a1a8");
+    assert_eq!(mate.outcome(), Some(Outcome::Checkmate {winner: Color::White}));
+    let no_mate = game("6k1/5ppp/8/8/8/8/8/R5K1 w - - 149 100", "a1a2");
+    assert_eq!(no_mate.outcome(), Some(Outcome::Draw(DrawReason::SeventyFiveMoveRule)));
+}
+
+#[test]
+fn draws() {
+    let stalemate = game("7k/8/6Q1/8/8/8/8/K7 b - - 0 1", "");
+    assert_eq!(stalemate.outcome(), Some(Outcome::Draw(DrawReason::Stalemate)));
+    let bare_kings = game("4k3/8/8/8/8/8/3q4/4K3 w - - 0 1", "e1d2");
+    assert_eq!(bare_kings.outcome(), Some(Outcome::Draw(DrawReason::InsufficientMaterial)));
+    let fifty = game("4k3/8/8/8/8/8/8/R3K3 w - - 99 80", "a1a2");
+    assert_eq!(fifty.outcome(), Some(Outcome::Draw(DrawReason::FiftyMoveRule)));
+
+    let shuffle = "g1f3 g8f6 f3g1 f6g8";
+    let threefold = game(chess::board::START_FEN, &[shuffle; 2].join(" "));
+    assert_eq!(threefold.outcome(), Some(Outcome::Draw(DrawReason::ThreefoldRepetition)));
+    let fivefold = game(chess::board::START_FEN, &[shuffle; 4].join(" "));
+    assert_eq!(fivefold.outcome(), Some(Outcome::Draw(DrawReason::FivefoldRepetition)));
+}
+
+#[test]
+fn only_some_draws_are_automatic() {
+    assert!(DrawReason::FivefoldRepetition.is_automatic());
+    assert!(DrawReason::Stalemate.is_automatic());
+    assert!(!DrawReason::ThreefoldRepetition.is_automatic());
+    assert!(!DrawReason::FiftyMoveRule.is_automatic());
+}
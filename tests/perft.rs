@@ -0,0 +1,115 @@
+//! Reference perft results from https://www.chessprogramming.org/Perft_Results
+//! The deepest counts take a while without optimizations, so they're ignored by default. Run them with `cargo test --release -- --ignored`.
+
+use chess::board::{Board, START_FEN};
+use chess::perft::{divide, perft, perft_stats, PerftStats};
+
+const KIWIPETE: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+const POSITION_3: &str = "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1";
+const POSITION_4: &str = "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1";
+const POSITION_4_MIRRORED: &str = "r2q1rk1/pP1p2pp/Q4n2/bbp1p3/Np6/1B3NBn/pPPP1PPP/R3K2R b KQ - 0 1";
+const POSITION_5: &str = "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8";
+const POSITION_6: &str = "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10";
+
+fn assert_perft(fen: &str, expected: &[u64]) {
+    let board = Board::from_fen(fen).unwrap();
+    for (depth, &nodes) in expected.iter().enumerate() {
+        assert_eq!(perft(&board, depth as u32 + 1), nodes, "depth {} of {fen}", depth + 1);
+    }
+}
+
+fn stats(nodes: u64, captures: u64, en_passants: u64, castles: u64, promotions: u64, checks: u64, checkmates: u64) -> PerftStats {
+    PerftStats {nodes, captures, en_passants, castles, promotions, checks, checkmates}
+}
+
+#[test]
+fn start_position() {
+    assert_perft(START_FEN, &[20, 400, 8902, 197281]);
+}
+
+#[test]
+fn kiwipete() {
+    assert_perft(KIWIPETE, &[48, 2039, 97862]);
+}
+
+#[test]
+fn position_3() {
+    assert_perft(POSITION_3, &[14, 191, 2812, 43238]);
+}
+
+#[test]
+fn position_4() {
+    assert_perft(POSITION_4, &[6, 264, 9467]);
+    assert_perft(POSITION_4_MIRRORED, &[6, 264, 9467]);
+}
+
+#[test]
+fn position_5() {
+    assert_perft(POSITION_5, &[44, 1486, 62379]);
+}
+
+#[test]
+fn position_6() {
+    assert_perft(POSITION_6, &[46, 2079, 89890]);
+}
+
+#[test]
+fn start_position_stats() {
+    let board = Board::from_fen(START_FEN).unwrap();
+    assert_eq!(perft_stats(&board, 3), stats(8902, 34, 0, 0, 0, 12, 0));
+}
+
+#[test]
+fn kiwipete_stats() {
+    let board = Board::from_fen(KIWIPETE).unwrap();
+    assert_eq!(perft_stats(&board, 2), stats(2039, 351, 1, 91, 0, 3, 0));
+}
+
+#[test]
+fn position_3_stats() {
+    let board = Board::from_fen(POSITION_3).unwrap();
+    assert_eq!(perft_stats(&board, 4), stats(43238, 3348, 123, 0, 0, 1680, 17));
+}
+
+#[test]
+fn position_4_stats() {
+    let board = Board::from_fen(POSITION_4).unwrap();
+    assert_eq!(perft_stats(&board, 3), stats(9467, 1021, 4, 0, 120, 38, 22));
+}
+
+#[test]
+fn divide_adds_up_to_perft() {
+    let board = Board::from_fen(KIWIPETE).unwrap();
+    let split = divide(&board, 3);
+    assert_eq!(split.len(), 48);
+    assert_eq!(split.iter().map(|(_, nodes)| nodes).sum::<u64>(), 97862);
+}
+
+#[test]
+fn divide_at_depth_zero_is_empty() {
+    let board = Board::from_fen(KIWIPETE).unwrap();
+    assert_eq!(divide(&board, 0), []);
+    assert_eq!(perft(&board, 0), 1);
+}
+
+#[test]
+#[ignore]
+fn deep_reference_counts() {
+    assert_perft(START_FEN, &[20, 400, 8902, 197281, 4865609]);
+    assert_perft(KIWIPETE, &[48, 2039, 97862, 4085603]);
+    assert_perft(POSITION_3, &[14, 191, 2812, 43238, 674624, 11030083]);
+    assert_perft(POSITION_4, &[6, 264, 9467, 422333, 15833292]);
+    assert_perft(POSITION_5, &[44, 1486, 62379, 2103487]);
+    assert_perft(POSITION_6, &[46, 2079, 89890, 3894594]);
+}
+
+#[test]
+#[ignore]
+fn deep_reference_stats() {
+    let board = Board::from_fen(START_FEN).unwrap();
+    assert_eq!(perft_stats(&board, 4), stats(197281, 1576, 0, 0, 0, 469, 8));
+    let board = Board::from_fen(KIWIPETE).unwrap();
+    assert_eq!(perft_stats(&board, 3), stats(97862, 17102, 45, 3162, 0, 993, 1));
+    let board = Board::from_fen(POSITION_4).unwrap();
+    assert_eq!(perft_stats(&board, 4), stats(422333, 131393, 0, 7795, 60032, 15492, 5));
+}
@@ -0,0 +1,124 @@
+use chess::board::Board;
+use chess::game::{Game, IllegalMove};
+use chess::movegen::legal_moves;
+use chess::pgn::{read_pgn, PgnError, PgnGame, PgnResult};
+use chess::rng::Rng;
+use chess::uci::Move;
+
+mod common;
+use common::line;
+
+const ANNOTATED: &str = r#"[Event "Casual \"blitz\" game"]
+[Site "?"]
+[White "Alice"]
+[Black "Bob"]
+[Result "1-0"]
+
+% a line meant for some other program
+1. e4 {The king's pawn} e5 2. Nf3 $1 Nc6 (2... d6 3. d4 (3. Bc4 Be7) exd4) 3. Bb5!? a6
+; a comment to the end of the line
+4.Ba4 Nf6 5. O-O Be7 6...? 1-0
+
+[Event "Second"]
+[FEN "4k3/8/8/8/8/8/4P3/4K3 b - - 0 30"]
+
+30... Kd7 31. e4 Ke6 *
+"#;
+
+#[test]
+fn reads_annotated_games() {
+    let games = read_pgn(ANNOTATED).unwrap();
+    assert_eq!(games.len(), 2);
+
+    let first = &games[0];
+    assert_eq!(first.tag("Event"), Some("Casual \"blitz\" game"));
+    assert_eq!(first.tag("White"), Some("Alice"));
+    assert_eq!(first.moves, line("e2e4 e7e5 g1f3 b8c6 f1b5 a7a6 b5a4 g8f6 e1g1 f8e7"));
+    assert_eq!(first.result, PgnResult::WhiteWins);
+    assert_eq!(first.game().unwrap().moves().len(), 10);
+
+    let second = &games[1];
+    assert_eq!(second.start, Board::from_fen("4k3/8/8/8/8/8/4P3/4K3 b - - 0 30").unwrap());
+    assert_eq!(second.moves, line("e8d7 e2e4 d7e6"));
+    assert_eq!(second.result, PgnResult::Unknown);
+}
+
+#[test]
+fn reports_bad_pgn() {
+    assert!(matches!(read_pgn("1. e4 e5 2. Ke3 *"), Err(PgnError::InvalidMove {ply: 3, ..})));
+    assert_eq!(read_pgn("1. e4 {never closed"), Err(PgnError::Unterminated('{')));
+    assert_eq!(read_pgn("1. e4 (1. d4 *"), Err(PgnError::UnbalancedVariation));
+    assert_eq!(read_pgn("[Event]"), Err(PgnError::InvalidTag("Event".to_string())));
+}
+
+#[test]
+fn skips_separate_en_passant_marks() {
+    let games = read_pgn("[FEN \"4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1\"]\n\n1. exd6 e.p. Kd7 2. Kd2 ep *").unwrap();
+    assert_eq!(games[0].moves, line("e5d6 e8d7 e1d2"));
+}
+
+#[test]
+fn rejects_null_moves() {
+    // Passing while in check would let the king be captured
+    assert!(matches!(read_pgn("1. e4 f6 2. Qh5+ -- 3. Qxe8 Kd1 *"), Err(PgnError::InvalidMove {ply: 4, ..})));
+    assert!(matches!(read_pgn("1. e4 -- 2. d4 *"), Err(PgnError::InvalidMove {ply: 2, ..})));
+}
+
+#[test]
+fn replaying_edited_moves_can_fail() {
+    let mut pgn = read_pgn("1. e4 e5 *").unwrap().remove(0);
+    pgn.moves.push(Move::Null);
+    assert_eq!(pgn.game(), Err(IllegalMove(Move::Null)));
+}
+
+#[test]
+fn writes_the_seven_tag_roster() {
+    let mut game = Game::default();
+    for m in line("f2f3 e7e5 g2g4 d8h4") {
+        game.play(m).unwrap();
+    }
+    let mut pgn = PgnGame::new(&game);
+    pgn.set_tag("White", "Random Mover");
+    pgn.set_tag("Annotator", "Nobody");
+    let expected = r#"[Event "?"]
+[Site "?"]
+[Date "????.??.??"]
+[Round "?"]
+[White "Random Mover"]
+[Black "?"]
+[Result "0-1"]
+[Annotator "Nobody"]
+
+1. f3 e5 2. g4 Qh4# 0-1
+"#;
+    assert_eq!(pgn.to_string(), expected);
+}
+
+#[test]
+fn written_games_read_back_the_same() {
+    let board = Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R b KQkq - 0 1").unwrap();
+    let mut game = Game::new(board);
+    for m in line("e8g8 e1c1") {
+        game.play(m).unwrap();
+    }
+    // Plenty of random moves so the movetext has to wrap
+    let mut rng = Rng::new(1);
+    while game.moves().len() < 60 && game.outcome().is_none() {
+        let m = *rng.choose(&legal_moves(game.board())).unwrap();
+        game.play(m).unwrap();
+    }
+    let mut pgn = PgnGame::new(&game);
+    pgn.set_tag("Event", "Round trip");
+    let text = pgn.to_string();
+    assert!(text.contains("[SetUp \"1\"]"));
+    assert!(text.contains("\n\n1... O-O 2. O-O-O "), "{text}");
+    assert!(text.lines().all(|line| line.len() < 80), "{text}");
+    assert!(text.lines().filter(|line| !line.starts_with('[')).count() > 2, "{text}");
+
+    let read = read_pgn(&text).unwrap();
+    assert_eq!(read.len(), 1);
+    assert_eq!(read[0].start, pgn.start);
+    assert_eq!(read[0].moves, pgn.moves);
+    assert_eq!(read[0].tag("Event"), Some("Round trip"));
+    assert_eq!(read[0].result, pgn.result);
+}
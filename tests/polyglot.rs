@@ -0,0 +1,84 @@
+use chess::board::Board;
+use chess::polyglot::{self, Book, BookChoice, BookEntry, BookError};
+use chess::rng::Rng;
+use chess::uci::Move;
+
+/// Packs a move the way Polyglot stores it.
+fn raw_move(m: &str) -> u16 {
+    let bytes = m.as_bytes();
+    let square = |file: u8, rank: u8| ((rank - b'1') << 3 | (file - b'a')) as u16;
+    square(bytes[0], bytes[1]) << 6 | square(bytes[2], bytes[3])
+}
+
+fn book(entries: &[BookEntry]) -> Book {
+    let bytes: Vec<u8> = entries.iter().flat_map(|entry| entry.to_bytes()).collect();
+    Book::from_bytes(&bytes).unwrap()
+}
+
+#[test]
+fn keys_match_the_reference() {
+    // The examples from the Polyglot book format description
+    let examples = [
+        ("", 0x463b96181691fc9c),
+        ("e2e4", 0x823c9b50fd114196),
+        ("e2e4 d7d5", 0x0756b94461c50fb0),
+        ("e2e4 d7d5 e4e5", 0x662fafb965db29d4),
+        ("e2e4 d7d5 e4e5 f7f5", 0x22a48b5a8e47ff78),
+        ("e2e4 d7d5 e4e5 f7f5 e1e2", 0x652a607ca3f242c1),
+        ("e2e4 d7d5 e4e5 f7f5 e1e2 e8f7", 0x00fdd303c946bdd9),
+        ("a2a4 b7b5 h2h4 b5b4 c2c4", 0x3c8123ea7b067637),
+        ("a2a4 b7b5 h2h4 b5b4 c2c4 b4c3 a1a3", 0x5c3f9b829b279560)
+    ];
+    for (moves, expected) in examples {
+        let mut board = Board::start_position();
+        for m in moves.split_whitespace() {
+            board.play(m.parse().unwrap());
+        }
+        assert_eq!(polyglot::key(&board), expected, "after \"{moves}\"");
+    }
+}
+
+#[test]
+fn castling_is_stored_as_capturing_the_rook() {
+    let board = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+    assert_eq!(polyglot::decode_move(&board, raw_move("e1h1")), "e1g1".parse::<Move>().unwrap());
+    assert_eq!(polyglot::decode_move(&board, raw_move("e1a1")), "e1c1".parse::<Move>().unwrap());
+    // A rook on e1 moving to h1 is just a rook move
+    let board = Board::from_fen("7k/8/8/8/8/8/8/K3R3 w - - 0 1").unwrap();
+    assert_eq!(polyglot::decode_move(&board, raw_move("e1h1")), "e1h1".parse::<Move>().unwrap());
+}
+
+#[test]
+fn promotions_are_decoded() {
+    let board = Board::from_fen("4k3/1P6/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    assert_eq!(polyglot::decode_move(&board, raw_move("b7b8") | 4 << 12), "b7b8q".parse::<Move>().unwrap());
+    assert_eq!(polyglot::decode_move(&board, raw_move("b7b8") | 1 << 12), "b7b8n".parse::<Move>().unwrap());
+}
+
+#[test]
+fn finds_moves_for_a_position() {
+    let start = Board::start_position();
+    let key = polyglot::key(&start);
+    let book = book(&[
+        BookEntry {key: key.wrapping_add(1), raw_move: raw_move("a2a3"), weight: 100, learn: 0},
+        BookEntry {key, raw_move: raw_move("d2d4"), weight: 5, learn: 0},
+        BookEntry {key, raw_move: raw_move("e2e4"), weight: 10, learn: 0},
+        BookEntry {key, raw_move: raw_move("g1f3"), weight: 0, learn: 0},
+        BookEntry {key: key.wrapping_sub(1), raw_move: raw_move("h2h3"), weight: 100, learn: 0}
+    ]);
+    assert_eq!(book.len(), 5);
+    assert_eq!(book.moves(&start), [("d2d4".parse().unwrap(), 5), ("e2e4".parse().unwrap(), 10), ("g1f3".parse().unwrap(), 0)]);
+
+    let mut rng = Rng::new(0);
+    assert_eq!(book.choose(&start, BookChoice::Best, &mut rng), Some("e2e4".parse().unwrap()));
+    for _ in 0..100 {
+        let m = book.choose(&start, BookChoice::Weighted, &mut rng).unwrap();
+        assert!(m == "d2d4".parse().unwrap() || m == "e2e4".parse().unwrap(), "{m} has no weight");
+    }
+    assert_eq!(book.choose(&Board::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap(), BookChoice::Best, &mut rng), None);
+}
+
+#[test]
+fn rejects_partial_entries() {
+    assert!(matches!(Book::from_bytes(&[0; 20]), Err(BookError::WrongLength(20))));
+}
@@ -0,0 +1,39 @@
+use chess::board::{Board, FenError};
+use chess::history::History;
+use chess::uci::{Position, PositionBase, PositionError};
+
+mod common;
+use common::{line, uci};
+
+fn startpos(moves: &str) -> Position {
+    Position {base: PositionBase::StartPosition, moves: line(moves)}
+}
+
+#[test]
+fn replays_the_moves() {
+    let game = startpos("e2e4 e7e5 g1f3").replay().unwrap();
+    assert_eq!(game.moves(), line("e2e4 e7e5 g1f3"));
+    assert_eq!(game.board(), &Board::from_fen("rnbqkbnr/pppp1ppp/8/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2").unwrap());
+}
+
+#[test]
+fn reports_which_move_is_illegal() {
+    // The third move, e5e4, is blocked by the pawn on e4
+    assert_eq!(startpos("e2e4 e7e5 e5e4 d2d4").replay(), Err(PositionError::IllegalMove {index: 2, illegal_move: uci("e5e4")}));
+    assert_eq!(startpos("e2e5").replay(), Err(PositionError::IllegalMove {index: 0, illegal_move: uci("e2e5")}));
+}
+
+#[test]
+fn reports_bad_fens() {
+    let position = Position {base: PositionBase::Fen("4k3/8/8/8/8/8/8/4K3 x - - 0 1".to_string()), moves: Vec::new()};
+    assert_eq!(position.replay(), Err(PositionError::InvalidFen(FenError::InvalidSideToMove("x".to_string()))));
+}
+
+#[test]
+fn keeps_the_history_for_repetitions() {
+    let game = startpos("g1f3 g8f6 f3g1 f6g8 g1f3 g8f6 f3g1 f6g8").replay().unwrap();
+    assert_eq!(game.positions().len(), 9);
+    assert_eq!(game.repetitions(), 3);
+    assert!(game.is_threefold_repetition());
+    assert!(History::from_game(&game).is_repetition(game.board().halfmove_clock()));
+}
@@ -0,0 +1,82 @@
+use chess::board::Board;
+use chess::san::{line_to_san, parse_san, to_san, SanError};
+use chess::uci::Move;
+
+mod common;
+use common::uci;
+
+/// Checks the move is written as expected, and that the SAN reads back as the same move.
+fn check(fen: &str, m: &str, expected: &str) {
+    let board = Board::from_fen(fen).unwrap();
+    assert_eq!(to_san(&board, uci(m)), expected, "{m} in {fen}");
+    assert_eq!(parse_san(&board, expected), Ok(uci(m)), "{expected} in {fen}");
+}
+
+#[test]
+fn writes_simple_moves() {
+    let start = chess::board::START_FEN;
+    check(start, "e2e4", "e4");
+    check(start, "g1f3", "Nf3");
+    check("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1", "e1g1", "O-O");
+    check("r3k2r/8/8/8/8/8/8/R3K2R b KQkq - 0 1", "e8c8", "O-O-O");
+    check("rnbqkbnr/ppp1pppp/8/3p4/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 2", "e4d5", "exd5");
+    check("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1", "e5d6", "exd6");
+}
+
+#[test]
+fn disambiguates() {
+    // Knights on b8 and f6 can both reach d7, and differ by file
+    check("1n2k3/8/5n2/8/8/8/8/4K3 b - - 0 1", "b8d7", "Nbd7");
+    // Rooks on a1 and a5 share the file, so the rank is used
+    check("4k3/8/8/R7/8/8/8/R3K3 w - - 0 1", "a1a3", "R1a3");
+    // Queens on a1, a3 and c1 can all reach b2, and a1 shares a file with one and a rank with the other
+    check("4k3/8/8/8/8/Q7/8/Q1Q1K3 w - - 0 1", "a1b2", "Qa1b2");
+    // A pinned knight doesn't count
+    check("4k3/8/8/8/1b6/8/3N1N2/4K3 w - - 0 1", "f2e4", "Ne4");
+}
+
+#[test]
+fn marks_promotions_checks_and_mates() {
+    check("8/4P3/8/8/8/8/k7/6K1 w - - 0 1", "e7e8q", "e8=Q");
+    check("2k5/4P3/8/8/8/8/8/6K1 w - - 0 1", "e7e8q", "e8=Q+");
+    check("2k5/4P3/8/8/8/8/8/6K1 w - - 0 1", "e7e8n", "e8=N");
+    check("r1bqkb1r/pppp1ppp/2n2n2/4p2Q/2B1P3/8/PPPP1PPP/RNB1K1NR w KQkq - 4 4", "h5f7", "Qxf7#");
+    check("4k3/8/8/8/8/8/8/R3K3 w Q - 0 1", "e1c1", "O-O-O");
+    check("4k3/8/8/8/8/8/8/4K2R w K - 0 1", "e1g1", "O-O");
+    check("4k3/8/8/8/8/8/8/3RK2R w K - 0 1", "h1h8", "Rh8+");
+}
+
+#[test]
+fn reads_lenient_forms() {
+    let board = Board::from_fen("r3k2r/8/8/3pP3/8/8/8/R3K2R w KQkq d6 0 1").unwrap();
+    assert_eq!(parse_san(&board, "exd6 e.p."), Ok(uci("e5d6")));
+    assert_eq!(parse_san(&board, "ed6"), Ok(uci("e5d6")));
+    assert_eq!(parse_san(&board, "0-0"), Ok(uci("e1g1")));
+    assert_eq!(parse_san(&board, "O-O-O!?"), Ok(uci("e1c1")));
+    assert_eq!(parse_san(&board, "Ra1-a7"), Ok(uci("a1a7")));
+    assert_eq!(parse_san(&board, "rxa8+"), Ok(uci("a1a8")));
+    assert_eq!(parse_san(&board, "e1f1"), Ok(uci("e1f1")));
+    assert_eq!(parse_san(&board, "Ke1f2"), Ok(uci("e1f2")));
+
+    let promotion = Board::from_fen("1n2k3/P7/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    assert_eq!(parse_san(&promotion, "a8Q"), Ok(uci("a7a8q")));
+    assert_eq!(parse_san(&promotion, "axb8=N"), Ok(uci("a7b8n")));
+    assert_eq!(parse_san(&promotion, "axb8b"), Ok(uci("a7b8b")));
+}
+
+#[test]
+fn rejects_bad_moves() {
+    let board = Board::start_position();
+    assert_eq!(parse_san(&board, "e5"), Err(SanError::IllegalMove("e5".to_string())));
+    assert_eq!(parse_san(&board, "O-O"), Err(SanError::IllegalMove("O-O".to_string())));
+    assert_eq!(parse_san(&board, "Zz9"), Err(SanError::Invalid("Zz9".to_string())));
+    assert_eq!(parse_san(&board, ""), Err(SanError::Invalid(String::new())));
+    let knights = Board::from_fen("1n2k3/8/5n2/8/8/8/8/4K3 b - - 0 1").unwrap();
+    assert_eq!(parse_san(&knights, "Nd7"), Err(SanError::Ambiguous("Nd7".to_string())));
+}
+
+#[test]
+fn writes_a_line() {
+    let moves: Vec<Move> = ["e2e4", "e7e5", "g1f3", "b8c6", "f1b5", "a7a6", "b5c6", "d7c6", "e1g1"].map(uci).to_vec();
+    assert_eq!(line_to_san(&Board::start_position(), &moves), ["e4", "e5", "Nf3", "Nc6", "Bb5", "a6", "Bxc6", "dxc6", "O-O"]);
+}
@@ -0,0 +1,43 @@
+use std::time::Duration;
+
+use chess::uci::{GUICommand, LimitsError, SearchLimits};
+
+mod common;
+use common::line;
+
+fn limits(go: &str) -> Result<SearchLimits, LimitsError> {
+    let GUICommand::Go(subcommands) = go.parse().unwrap() else {
+        panic!("{go} is not a go command");
+    };
+    SearchLimits::from_go(&subcommands)
+}
+
+#[test]
+fn fills_in_every_limit() {
+    let parsed = limits("go searchmoves e2e4 d2d4 wtime 1000 btime 2000 winc 10 binc 20 movestogo 5").unwrap();
+    assert_eq!(parsed, SearchLimits {
+        search_moves: line("e2e4 d2d4"),
+        white_time: Some(Duration::from_millis(1000)),
+        black_time: Some(Duration::from_millis(2000)),
+        white_increment: Some(Duration::from_millis(10)),
+        black_increment: Some(Duration::from_millis(20)),
+        moves_to_go: Some(5),
+        ..SearchLimits::default()
+    });
+    assert_eq!(limits("go"), Ok(SearchLimits::default()));
+}
+
+#[test]
+fn rejects_duplicates() {
+    assert_eq!(limits("go depth 3 depth 4"), Err(LimitsError::Duplicate("depth")));
+    assert_eq!(limits("go infinite nodes 5 infinite"), Err(LimitsError::Duplicate("infinite")));
+}
+
+#[test]
+fn infinite_conflicts_with_other_limits() {
+    assert_eq!(limits("go infinite movetime 100"), Err(LimitsError::Conflict("infinite", "movetime")));
+    assert_eq!(limits("go depth 5 infinite"), Err(LimitsError::Conflict("infinite", "depth")));
+    // Restricting the moves or pondering doesn't limit how long the search goes on
+    assert!(limits("go infinite searchmoves e2e4").is_ok());
+    assert!(limits("go ponder infinite").is_ok());
+}
@@ -0,0 +1,64 @@
+use chess::uci::{GUICommand, GoCommand, ParseError, Position, PositionBase};
+
+mod common;
+use common::{line, uci};
+
+fn parse(text: &str) -> Result<GUICommand, ParseError> {
+    text.parse()
+}
+
+#[test]
+fn skips_unknown_leading_tokens() {
+    assert_eq!(parse("joho debug on"), Ok(GUICommand::DebugMode(true)));
+    assert_eq!(parse("  isready  "), Ok(GUICommand::IsReady));
+    assert_eq!(parse("joho"), Err(ParseError::NoCommand));
+    assert_eq!(parse(""), Err(ParseError::NoCommand));
+}
+
+#[test]
+fn reads_setoption() {
+    assert_eq!(parse("setoption name Clear Hash Now value two words"), Ok(GUICommand::SetEngineParameter {
+        option_name: "Clear Hash Now".to_string(),
+        option_value: Some("two words".to_string())
+    }));
+    // Buttons have no value at all, which isn't the same as an empty one
+    assert_eq!(parse("setoption name Clear Hash"), Ok(GUICommand::SetEngineParameter {
+        option_name: "Clear Hash".to_string(),
+        option_value: None
+    }));
+}
+
+#[test]
+fn reads_position() {
+    let fen = "4k3/8/8/8/8/8/4P3/4K3 w - - 0 1";
+    assert_eq!(parse(&format!("position fen {fen} moves e2e4 e8d7")), Ok(GUICommand::Position(Position {
+        base: PositionBase::Fen(fen.to_string()),
+        moves: line("e2e4 e8d7")
+    })));
+    assert_eq!(parse("position startpos"), Ok(GUICommand::Position(Position {base: PositionBase::StartPosition, moves: Vec::new()})));
+}
+
+#[test]
+fn reads_go() {
+    assert_eq!(parse("go searchmoves e2e4 d2d4 depth 5 infinite"), Ok(GUICommand::Go(vec![
+        GoCommand::SearchMoves(vec![uci("e2e4"), uci("d2d4")]),
+        GoCommand::MaxSearchDepth(5),
+        GoCommand::InfiniteSearch
+    ])));
+    // A flag fallen clock is sometimes sent as a negative time
+    assert_eq!(parse("go wtime -150 btime 3000"), Ok(GUICommand::Go(vec![
+        GoCommand::WhiteClockLeft(0),
+        GoCommand::BlackClockLeft(3000)
+    ])));
+}
+
+#[test]
+fn errors_name_the_offending_token() {
+    assert_eq!(parse("debug maybe"), Err(ParseError::UnexpectedToken("maybe".to_string())));
+    assert_eq!(parse("debug"), Err(ParseError::MissingArgument("debug")));
+    assert_eq!(parse("setoption name"), Err(ParseError::MissingArgument("name")));
+    assert_eq!(parse("position startpos e2e4"), Err(ParseError::UnexpectedToken("e2e4".to_string())));
+    assert_eq!(parse("go depth five"), Err(ParseError::InvalidNumber("five".to_string())));
+    assert_eq!(parse("go movetime"), Err(ParseError::MissingArgument("movetime")));
+    assert_eq!(parse("position startpos moves e2e4 e7e9"), Err(ParseError::InvalidMove("e7e9".to_string())));
+}
@@ -0,0 +1,126 @@
+use std::io::{BufReader, Cursor, PipeWriter, Write};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use chess::board::Board;
+use chess::engines::random::RandomMover;
+use chess::polyglot::{self, BookEntry};
+use chess::rng::Rng;
+use chess::uci::UCIInterface;
+
+/// Feeds the whole input to a random mover and returns everything it wrote.
+fn run(input: &str) -> Vec<String> {
+    let mut output = Vec::new();
+    UCIInterface::new(RandomMover::new(Rng::new(0)), Cursor::new(input.to_string()), &mut output).run().unwrap();
+    String::from_utf8(output).unwrap().lines().map(str::to_string).collect()
+}
+
+#[test]
+fn handshake() {
+    let output = run("uci\nisready\nquit\n");
+    assert_eq!(output, [
+        "id name RustyChess Random Mover",
+        "id author LilyIsTrans",
+        "option name OwnBook type check default false",
+        "option name BookFile type string default <empty>",
+        "uciok",
+        "readyok"
+    ]);
+}
+
+#[test]
+fn answers_go_with_a_legal_move() {
+    let output = run("position fen 7k/8/8/8/8/8/8/K7 w - - 0 1\ngo movetime 10\n");
+    let king_moves = ["bestmove a1a2", "bestmove a1b1", "bestmove a1b2"];
+    assert_eq!(output.len(), 1);
+    assert!(king_moves.contains(&output[0].as_str()), "{output:?}");
+}
+
+/// Collects what the interface writes, so it can be checked while the interface is still running.
+#[derive(Clone, Default)]
+struct SharedOutput(Arc<Mutex<Vec<u8>>>);
+
+impl Write for SharedOutput {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A random mover fed through a pipe that stays open, so only the commands sent can end a search.
+struct Session {
+    input: PipeWriter,
+    output: SharedOutput
+}
+
+impl Session {
+    fn start() -> Session {
+        let (reader, input) = std::io::pipe().unwrap();
+        let output = SharedOutput::default();
+        let interface = UCIInterface::new(RandomMover::new(Rng::new(0)), BufReader::new(reader), output.clone());
+        std::thread::spawn(move || interface.run().unwrap());
+        Session {input, output}
+    }
+
+    fn send(&mut self, commands: &str) {
+        self.input.write_all(commands.as_bytes()).unwrap();
+    }
+
+    fn lines(&self) -> Vec<String> {
+        String::from_utf8(self.output.0.lock().unwrap().clone()).unwrap().lines().map(str::to_string).collect()
+    }
+
+    /// Waits up to a second for a line starting with `prefix`, then returns everything written so far.
+    fn wait_for(&self, prefix: &str) -> Vec<String> {
+        let deadline = Instant::now() + Duration::from_secs(1);
+        while Instant::now() < deadline && !self.lines().iter().any(|line| line.starts_with(prefix)) {
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        self.lines()
+    }
+}
+
+#[test]
+fn stop_ends_an_infinite_search() {
+    let mut session = Session::start();
+    session.send("position startpos moves e2e4\ngo infinite\n");
+    assert_eq!(session.wait_for("bestmove"), Vec::<String>::new());
+    session.send("stop\n");
+    let output = session.wait_for("bestmove");
+    assert_eq!(output.len(), 1);
+    assert!(output[0].starts_with("bestmove "), "{output:?}");
+    session.send("quit\n");
+}
+
+#[test]
+fn answers_isready_and_stop_behind_a_held_back_command() {
+    let mut session = Session::start();
+    // The position can't be set until the search ends, but the commands after it mustn't wait for it
+    session.send("go infinite\nposition startpos moves e2e4\nisready\n");
+    assert_eq!(session.wait_for("readyok"), ["readyok"]);
+    session.send("stop\n");
+    let output = session.wait_for("bestmove");
+    assert_eq!(output.len(), 2);
+    assert!(output[1].starts_with("bestmove "), "{output:?}");
+    session.send("quit\n");
+}
+
+#[test]
+fn answers_go_from_the_book() {
+    // A one entry book that always answers 1. e4 with c5
+    let mut board = Board::start_position();
+    board.play("e2e4".parse().unwrap());
+    let c7c5 = (2 << 6) | (6 << 9) | 2 | (4 << 3);
+    let entry = BookEntry {key: polyglot::key(&board), raw_move: c7c5, weight: 1, learn: 0};
+    let path = std::env::temp_dir().join(format!("rustychess-test-book-{}.bin", std::process::id()));
+    std::fs::write(&path, entry.to_bytes()).unwrap();
+
+    let input = format!("setoption name BookFile value {}\nsetoption name OwnBook value true\nposition startpos moves e2e4\ngo movetime 10\n", path.display());
+    let output = run(&input);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(output, ["bestmove c7c5"]);
+}
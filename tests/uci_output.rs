@@ -0,0 +1,81 @@
+use std::io::Cursor;
+
+use chess::game::Game;
+use chess::uci::{BestMove, EngineCommand, EngineParameter, IdCommandData, InfoCommandData, Move, ScoreInfoData, SearchLimits, SearchSignals, TooManyInfoStrings, UCIInterface};
+
+mod common;
+use common::{line, uci};
+
+#[test]
+fn writes_simple_commands() {
+    assert_eq!(EngineCommand::ID(IdCommandData::Name("RustyChess".to_string())).to_string(), "id name RustyChess");
+    assert_eq!(EngineCommand::ID(IdCommandData::Author("LilyIsTrans".to_string())).to_string(), "id author LilyIsTrans");
+    assert_eq!(EngineCommand::EngineInitialized.to_string(), "uciok");
+    assert_eq!(EngineCommand::EngineReady.to_string(), "readyok");
+    let best = EngineCommand::MoveSelected {selected_move: uci("e2e4"), desired_ponder: Some(uci("e7e5"))};
+    assert_eq!(best.to_string(), "bestmove e2e4 ponder e7e5");
+    assert_eq!(EngineCommand::from(BestMove {selected_move: Move::Null, ponder: None}).to_string(), "bestmove 0000");
+}
+
+#[test]
+fn writes_options() {
+    let option = |name: &str, parameter| EngineCommand::Option {name: name.to_string(), parameter}.to_string();
+    assert_eq!(option("Hash", EngineParameter::Spin {default: 16, min: 1, max: 1024}), "option name Hash type spin default 16 min 1 max 1024");
+    assert_eq!(option("Clear Hash", EngineParameter::Button), "option name Clear Hash type button");
+    assert_eq!(option("BookFile", EngineParameter::String(String::new())), "option name BookFile type string default <empty>");
+}
+
+#[test]
+fn writes_info() {
+    let info = EngineCommand::info(vec![
+        InfoCommandData::InfoString("thinking hard".to_string()),
+        InfoCommandData::Depth(3),
+        InfoCommandData::Score(ScoreInfoData::CentiPawns(20)),
+        InfoCommandData::Score(ScoreInfoData::ScoreIsLowerBound),
+        InfoCommandData::PrincipleVariation(line("e2e4 e7e5"))
+    ]).unwrap();
+    // The bound follows its score, and the string goes last even though it came first
+    assert_eq!(info.to_string(), "info depth 3 score cp 20 lowerbound pv e2e4 e7e5 string thinking hard");
+    // Empty move lists leave no trailing space
+    let empty = EngineCommand::info(vec![InfoCommandData::PrincipleVariation(Vec::new())]).unwrap();
+    assert_eq!(empty.to_string(), "info pv");
+}
+
+#[test]
+fn info_holds_at_most_one_string() {
+    let strings = vec![InfoCommandData::InfoString("one".to_string()), InfoCommandData::InfoString("two".to_string())];
+    assert_eq!(EngineCommand::info(strings.clone()), Err(TooManyInfoStrings));
+    // Built directly, each extra string gets an info of its own rather than being run together
+    assert_eq!(EngineCommand::Info(strings).to_string(), "info string one\ninfo string two");
+}
+
+/// Reports two strings at once, which the front end has to split up.
+struct Chatty;
+
+impl chess::uci::Engine for Chatty {
+    fn name(&self) -> String {
+        "Chatty".to_string()
+    }
+
+    fn author(&self) -> String {
+        "Nobody".to_string()
+    }
+
+    fn set_position(&mut self, _game: Game) {}
+
+    fn go(&mut self, _limits: &SearchLimits, _signals: &SearchSignals, info: &mut dyn FnMut(Vec<InfoCommandData>)) -> BestMove {
+        info(vec![
+            InfoCommandData::InfoString("one".to_string()),
+            InfoCommandData::Depth(1),
+            InfoCommandData::InfoString("two".to_string())
+        ]);
+        BestMove {selected_move: uci("e2e4"), ponder: None}
+    }
+}
+
+#[test]
+fn front_end_sends_one_string_per_info() {
+    let mut output = Vec::new();
+    UCIInterface::new(Chatty, Cursor::new("go depth 1\n"), &mut output).run().unwrap();
+    assert_eq!(String::from_utf8(output).unwrap(), "info depth 1 string one\ninfo string two\nbestmove e2e4\n");
+}
@@ -0,0 +1,45 @@
+use chess::board::Board;
+use chess::uci::Move;
+
+fn play(board: &mut Board, moves: &str) {
+    for m in moves.split_whitespace() {
+        board.play(m.parse::<Move>().unwrap());
+    }
+}
+
+#[test]
+fn transpositions_have_the_same_key() {
+    let mut one = Board::start_position();
+    play(&mut one, "g1f3 g8f6 b1c3 b8c6");
+    let mut other = Board::start_position();
+    play(&mut other, "b1c3 b8c6 g1f3 g8f6");
+    assert_eq!(one.key(), other.key());
+
+    // Moving the knights out and back leaves the same position, apart from the move counters
+    let mut back = Board::start_position();
+    play(&mut back, "g1f3 g8f6 f3g1 f6g8");
+    assert_eq!(back.key(), Board::start_position().key());
+}
+
+#[test]
+fn unusable_en_passant_square_is_not_hashed() {
+    let mut board = Board::start_position();
+    play(&mut board, "e2e4");
+    let without = Board::from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1").unwrap();
+    assert_eq!(board.key(), without.key());
+
+    // Once a black pawn stands beside the pushed pawn the square matters
+    let with = Board::from_fen("rnbqkbnr/ppp1pppp/8/8/3pP3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1").unwrap();
+    let ignored = Board::from_fen("rnbqkbnr/ppp1pppp/8/8/3pP3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1").unwrap();
+    assert_ne!(with.key(), ignored.key());
+}
+
+#[test]
+fn different_positions_have_different_keys() {
+    let start = Board::start_position();
+    let black_to_move = Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR b KQkq - 0 1").unwrap();
+    let no_castling = Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w - - 0 1").unwrap();
+    assert_ne!(start.key(), black_to_move.key());
+    assert_ne!(start.key(), no_castling.key());
+    assert_ne!(black_to_move.key(), no_castling.key());
+}